@@ -0,0 +1,232 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/1
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc::math::two_sum;
+use aoc::numeric::Number;
+
+
+pub const TARGET_SUM: i32 = 2020;
+
+pub fn parse_numbers(lines: &[String]) -> Vec<i32> {
+    lines
+        .iter()
+        .map(|line| i32::from_str(line).unwrap())
+        .collect()
+}
+
+fn two_sum_part2(nums: &[i32], i: usize, target: i32) -> Option<(i32, i32, i32)> {
+    let (mut lo, mut hi) = (i+1, nums.len() - 1);
+
+    while lo < hi {
+        match (nums[i] + nums[lo] + nums[hi]).cmp(&target) {
+            Ordering::Less => { lo += 1; },
+            Ordering::Greater => { hi -= 1; },
+            Ordering::Equal => { return Some((nums[i], nums[lo], nums[hi])); }
+        }
+    }
+
+    None
+}
+
+fn three_sum_part2(numbers: &[i32], target: i32) -> (i32, i32, i32) {
+    let mut nums = numbers.to_vec();
+
+    nums.sort_unstable();
+
+    (0..nums.len()).find_map(|i| two_sum_part2(&nums, i, target)).unwrap()
+}
+
+pub fn part1(numbers: &[i32], target: i32) -> i32 {
+    let (a, b) = two_sum(numbers, target).unwrap();
+    a*b
+}
+
+pub fn part2(numbers: &[i32], target: i32) -> i32 {
+    let (c, d, e) = three_sum_part2(numbers, target);
+    c*d*e
+}
+
+fn search(
+    numbers: &[i32],
+    order: &[usize],
+    k: usize,
+    remaining: i64,
+    chosen: &mut Vec<usize>,
+    res: &mut Vec<Vec<usize>>,
+) {
+    if chosen.len() == k {
+        if remaining == 0 {
+            let mut indices = chosen.clone();
+            indices.sort_unstable();
+            res.push(indices);
+        }
+        return;
+    }
+
+    for (p, &i) in order.iter().enumerate() {
+        let n = numbers[i] as i64;
+        // Entries are sorted, so none of the next ones fits either
+        if n >= 0 && n > remaining {
+            break;
+        }
+
+        chosen.push(i);
+        search(numbers, &order[p+1..], k, remaining - n, chosen, res);
+        chosen.pop();
+    }
+}
+
+/// Every set of `k` entries that sum to `target`, as increasing indices
+/// into `numbers`, so that equal entries are told apart.
+pub fn combinations(numbers: &[i32], k: usize, target: i32) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    order.sort_by_key(|&i| numbers[i]);
+
+    let mut res = Vec::new();
+    search(numbers, &order, k, target as i64, &mut Vec::new(), &mut res);
+    res.sort();
+    res
+}
+
+/// Same as `combinations`, for every size in `sizes`.
+pub fn subsets(numbers: &[i32], sizes: RangeInclusive<usize>, target: i32) -> Vec<Vec<usize>> {
+    sizes.flat_map(|k| combinations(numbers, k, target)).collect()
+}
+
+/// How many sets of entries with a size in `sizes` sum to `target`, without
+/// listing them. `ways[k][s]` is how many sets of `k` of the entries seen so
+/// far sum to `s`.
+pub fn count_subsets<N: Number>(numbers: &[i32], sizes: RangeInclusive<usize>, target: i32) -> N {
+    let max = *sizes.end();
+    let mut ways: Vec<HashMap<i64, N>> = vec![HashMap::new(); max + 1];
+    ways[0].insert(0, N::one());
+
+    for &n in numbers.iter() {
+        for k in (1..=max).rev() {
+            let extended: Vec<(i64, N)> = ways[k-1]
+                .iter()
+                .map(|(s, c)| (s + n as i64, c.clone()))
+                .collect();
+
+            for (s, c) in extended {
+                let total = ways[k].entry(s).or_insert_with(N::zero);
+                *total = total.clone() + c;
+            }
+        }
+    }
+
+    sizes.fold(N::zero(), |acc, k| {
+        acc + ways[k].get(&(target as i64)).cloned().unwrap_or_else(N::zero)
+    })
+}
+
+/// The product of the entries at `indices`, or `None` if it doesn't fit in
+/// an `i64`, as with eight entries around 2020 / 8.
+pub fn product(numbers: &[i32], indices: &[usize]) -> Option<i64> {
+    indices.iter().try_fold(1_i64, |acc, &i| acc.checked_mul(numbers[i] as i64))
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let numbers = parse_numbers(lines);
+
+    match part {
+        1 => Some(part1(&numbers, TARGET_SUM).to_string()),
+        2 => Some(part2(&numbers, TARGET_SUM).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 1, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_sum_part2() {
+        assert_eq!(
+            (366, 675, 979),
+            three_sum_part2(&[1721, 979, 366, 299, 675, 1456], TARGET_SUM),
+        );
+    }
+
+    #[test]
+    fn test_combinations() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(vec![vec![0, 3]], combinations(&numbers, 2, TARGET_SUM));
+        assert_eq!(vec![vec![1, 2, 4]], combinations(&numbers, 3, TARGET_SUM));
+        assert_eq!(Some(514579), product(&numbers, &[0, 3]));
+
+        // Equal entries are different combinations
+        let numbers = [1010, 1010, 2000, 20, 2000];
+        assert_eq!(
+            vec![vec![0, 1], vec![2, 3], vec![3, 4]],
+            combinations(&numbers, 2, TARGET_SUM),
+        );
+
+        // Every subset, picked by the bits of a mask
+        let numbers = [2030, -10, 5, 2015, -6, -4, 0, 2020];
+        let brute_force: Vec<Vec<usize>> = (0..1_u32 << numbers.len())
+            .map(|mask| (0..numbers.len()).filter(|i| mask & (1 << i) != 0).collect::<Vec<_>>())
+            .filter(|indices| {
+                indices.len() >= 2 && indices.len() <= 4
+                    && indices.iter().map(|&i| numbers[i]).sum::<i32>() == TARGET_SUM
+            })
+            .collect();
+        let mut found = subsets(&numbers, 2..=4, TARGET_SUM);
+
+        found.sort();
+        assert_eq!(brute_force.len(), found.len());
+        assert!(brute_force.iter().all(|s| found.contains(s)));
+    }
+
+    #[test]
+    fn test_count_subsets() {
+        let numbers = [1010, 1010, 2000, 20, 2000, 1, 1019, 2020];
+
+        for sizes in [1..=1, 2..=2, 3..=3, 1..=4, 2..=8] {
+            assert_eq!(
+                subsets(&numbers, sizes.clone(), TARGET_SUM).len(),
+                count_subsets::<usize>(&numbers, sizes, TARGET_SUM),
+            );
+        }
+        assert_eq!(1, count_subsets::<usize>(&numbers, 1..=1, TARGET_SUM));
+    }
+
+    #[test]
+    fn test_product_overflow() {
+        let numbers = [252, 253, 252, 253, 252, 253, 252, 253];
+        let indices: Vec<usize> = (0..numbers.len()).collect();
+
+        assert_eq!(vec![indices.clone()], combinations(&numbers, 8, TARGET_SUM));
+        assert_eq!(None, product(&numbers, &indices));
+        assert_eq!(Some(252 * 253 * 252 * 253), product(&numbers, &indices[..4]));
+    }
+}
@@ -24,64 +24,79 @@
 
 #![allow(non_snake_case)]
 
-use std::cmp::Ordering;
-use std::str::FromStr;
-
+use aoc::config;
 use aoc::fs::get_file_contents;
-use aoc::math::two_sum;
-
-
-const TARGET_SUM: i32 = 2020;
-
-
-fn two_sum_part2(nums: &Vec<i32>, i: usize) -> Option<(i32, i32, i32)> {
-    let (mut lo, mut hi) = (i+1, nums.len() - 1);
+use aoc::numeric::Wide;
+use aoc::runner::Runner;
+use day1::{count_subsets, parse_numbers, part1, part2, product, subsets, TARGET_SUM};
+use serde::Deserialize;
+
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    target_sum: i32,
+    // Smallest and largest number of entries in the audit report
+    audit_sizes: Option<(usize, usize)>,
+}
 
-    while lo < hi {
-        match (nums[i] + nums[lo] + nums[hi]).cmp(&TARGET_SUM) {
-            Ordering::Less => { lo += 1; },
-            Ordering::Greater => { hi -= 1; },
-            Ordering::Equal => { return Some((nums[i], nums[lo], nums[hi])); }
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            target_sum: TARGET_SUM,
+            audit_sizes: None,
         }
     }
+}
 
-    None
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        match self.audit_sizes {
+            Some((lo, hi)) if lo == 0 || lo > hi => {
+                Err(format!("audit_sizes must be [min, max] with 1 <= min <= max, not [{}, {}]", lo, hi))
+            },
+            _ => Ok(()),
+        }
+    }
 }
 
-fn three_sum_part2(numbers: &Vec<i32>) -> (i32, i32, i32) {
-    let mut nums = numbers.clone();
+// Every set of entries that sums to the target, with their indices in the
+// input, so that equal entries are told apart.
+fn print_audit(numbers: &[i32], (lo, hi): (usize, usize), target: i32) {
+    let count: Wide<u64> = count_subsets(numbers, lo..=hi, target);
+    println!("Audit: {} sets of {} to {} entries sum to {}", count, lo, hi, target);
 
-    &nums.sort_unstable();
+    for indices in subsets(numbers, lo..=hi, target) {
+        let entries: Vec<String> = indices
+            .iter()
+            .map(|&i| format!("{} (#{})", numbers[i], i))
+            .collect();
 
-    (0..nums.len()).find_map(|i| two_sum_part2(&nums, i)).unwrap()
+        let product = match product(numbers, &indices) {
+            Some(p) => format!("product {}", p),
+            None => "product overflows i64".to_string(),
+        };
+
+        println!("    {} = {}, {}", entries.join(" + "), target, product);
+    }
 }
 
+
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(1);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
-    let numbers: Vec<i32> = lines
-        .iter()
-        .map(|line| i32::from_str(line).unwrap())
-        .collect();
+    let numbers = parse_numbers(&lines);
 
+    runner.part(1, || part1(&numbers, params.target_sum));
+    runner.part(2, || part2(&numbers, params.target_sum));
 
-    let (a, b) = two_sum(&numbers, TARGET_SUM).unwrap();
-    let (c, d, e) = three_sum_part2(&numbers);
+    if let Some(sizes) = params.audit_sizes {
+        print_audit(&numbers, sizes, params.target_sum);
+    }
 
-    println!("Day 1 / Part 1: {}", a*b);
-    println!("Day 1 / Part 2: {}", c*d*e);
+    runner.finish();
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_three_sum_part2() {
-        assert_eq!(
-            (366, 675, 979),
-            three_sum_part2(&vec![1721, 979, 366, 299, 675, 1456]),
-        );
-    }
-}
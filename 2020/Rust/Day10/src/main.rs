@@ -24,109 +24,51 @@
 
 #![allow(non_snake_case)]
 
-use std::str::FromStr;
-
+use aoc::config;
 use aoc::fs::get_file_contents;
+use aoc::numeric::Wide;
+use aoc::runner::Runner;
+use day10::{arrangements, jolt_distribution, parse_jolts, DIFF};
+use serde::Deserialize;
 
 
-const DIFF: usize = 3;
-
-
-fn arrangements(jolts: &Vec<usize>) -> usize {
-    let len = jolts.len();
-    let mut dp = vec![0; len];
-    dp[len-1] = 1;
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    diff: usize,
+}
 
-    for i in (0..len).rev() {
-        for j in 1..=DIFF.min((len-1)-i) {
-            if jolts[i+j] <= jolts[i] + DIFF {
-                dp[i] += dp[i+j];
-            }
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            diff: DIFF,
         }
     }
-
-    dp[0]
 }
 
-
-fn jolt_distribution(jolts: &Vec<usize>) -> (usize, usize, usize) {
-    (1..jolts.len()).fold((0, 0, 0), |(a, b, c), i| {
-        match jolts[i] - jolts[i-1] {
-            1 => (a+1, b, c),
-            2 => (a, b+1, c),
-            3 => (a, b, c+1),
-            _ => panic!("You got jolted!"),
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.diff == 0 {
+            return Err("diff must be at least 1".to_string());
         }
-    })
+
+        Ok(())
+    }
 }
 
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(10);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
-    let mut jolts: Vec<usize> = lines
-        .iter()
-        .map(|line| usize::from_str(line).unwrap())
-        .collect();
-
-    jolts.push(0); // charging outlet
-    jolts.push(jolts.iter().max().unwrap() + 3); // the device
-    &jolts.sort_unstable();
-
-    let (d1, _, d3) = jolt_distribution(&jolts);
+    let jolts = parse_jolts(&lines, params.diff);
 
-    println!("Day 10 / Part 1: {}", d1 * d3);
-    println!("Day 10 / Part 2: {}", arrangements(&jolts));
+    runner.part(1, || {
+        let (d1, _, d3) = jolt_distribution(&jolts);
+        d1 * d3
+    });
+    runner.part(2, || arrangements::<Wide<usize>>(&jolts, params.diff));
+    runner.finish();
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const ADAPTERS1: [usize; 13] = [
-        0, 16, 10, 15, 5, 1, 
-        11, 7, 19, 6, 12, 4,
-        22,
-    ];
-
-    const ADAPTERS2: [usize; 33] = [
-        28, 33, 18, 42, 31, 14,
-        46, 20, 48, 47, 24, 23,
-        49, 45, 19, 38, 39, 11,
-        1,  32, 25, 35, 8,  17,
-        7,  9,  4,  2,  34, 10,
-        3, 0, 52,
-    ];
-
-    #[test]
-    fn test_jolt_distribution1() {
-        let mut jolts = ADAPTERS1.to_vec().clone();
-        &jolts.sort_unstable();
-
-        assert_eq!((7, 0, 5), jolt_distribution(&jolts));
-    }
-
-    #[test]
-    fn test_jolt_distribution2() {
-        let mut jolts = ADAPTERS2.to_vec().clone();
-        &jolts.sort_unstable();
-
-        assert_eq!((22, 0, 10), jolt_distribution(&jolts));
-    }
-
-    #[test]
-    fn test_arrangements1() {
-        let mut jolts = ADAPTERS1.to_vec().clone();
-        &jolts.sort_unstable();
-
-        assert_eq!(8, arrangements(&jolts));
-    }
-
-    #[test]
-    fn test_arrangements2() {
-        let mut jolts = ADAPTERS2.to_vec().clone();
-        &jolts.sort_unstable();
-
-        assert_eq!(19208, arrangements(&jolts));
-    }
-}
@@ -0,0 +1,342 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/11
+
+use std::convert::TryFrom;
+
+use aoc::budget::{Budget, DidNotConverge};
+use aoc::sim::Simulation;
+
+
+// Occupied seats that make someone leave
+pub const ADJACENT_TOLERANCE: usize = 4;
+pub const VISIBLE_TOLERANCE: usize = 5;
+
+pub trait SeatSimulator {
+    fn floor_count(&mut self) -> usize;
+    fn change_pos(&mut self, i: usize, j: usize, c: char);
+    fn matrix(&mut self) -> &Vec<Vec<char>>;
+    fn tolerance(&mut self) -> usize;
+    fn ahead(&mut self, row: usize, col: usize, drow: i32, dcol: i32) -> (usize, usize);
+
+    fn occupied_neighbours(&mut self, row: i32, col: i32) -> usize {
+        let (rows, cols) = (self.matrix().len(), self.matrix()[0].len());
+        let mut neighbours = Vec::with_capacity(8);
+
+        for dcol in -1..=1 {
+            for drow in -1..=1 {
+                if drow == 0 && dcol == 0 { continue; }
+
+                match (usize::try_from(row + drow), usize::try_from(col + dcol)) {
+                    (Ok(i), Ok(j)) if i < rows && j < cols => {
+                        neighbours.push(self.ahead(i, j, drow, dcol));
+                    },
+                    _ => (),
+                }
+            }
+        }
+
+        neighbours.iter().filter(|(x, y)| self.matrix()[*x][*y] == '#').count()
+    }
+
+    fn must_occupy(&mut self, i: i32, j: i32) -> bool {
+        self.occupied_neighbours(i, j) == 0
+    }
+
+    fn must_vacate(&mut self, i: i32, j: i32) -> bool {
+        self.occupied_neighbours(i, j) >= self.tolerance()
+    }
+
+    fn single_round(&mut self) {
+        let (rows, cols) = (self.matrix().len(), self.matrix()[0].len());
+        let mut changes: Vec<(usize,usize,char)> =
+            Vec::with_capacity(rows*cols - self.floor_count());
+
+        for i in 0..rows {
+            for j in 0..cols {
+                match self.matrix()[i][j] {
+                    '.' => (),
+                    'L' => if self.must_occupy(i as i32, j as i32) {
+                        changes.push((i,j,'#'));
+                    },
+                    '#' => if self.must_vacate(i as i32, j as i32) {
+                        changes.push((i,j,'L'));
+                    },
+                    _ => panic!("Unknown char"),
+                }
+            }
+        }
+
+        for (i, j, c) in changes.iter() {
+            self.change_pos(*i, *j, *c);
+        }
+    }
+
+    // The seats stabilise once a round leaves the whole layout unchanged.
+    // Comparing only the number of occupied seats isn't enough, since two
+    // different layouts may have the same count.
+    fn occupied_seats(&mut self, budget: &mut Budget) -> Result<usize, DidNotConverge>
+    where
+        Self: Simulation
+    {
+        if let Err(e) = self.run_until_fixed_point_within(budget) {
+            return Err(e.with_state(format!(
+                "{} seats were occupied after {} rounds",
+                self.count_occupied(), budget.steps(),
+            )));
+        }
+
+        Ok(self.count_occupied())
+    }
+
+    fn count_occupied(&mut self) -> usize {
+        self.matrix()
+            .iter()
+            .map(|row| row.iter().filter(|&c| *c == '#').count())
+            .sum()
+    }
+}
+
+#[derive(Clone,Eq,Hash,PartialEq)]
+pub struct GridPart1 {
+    m: Vec<Vec<char>>,
+    fc: usize,
+    tolerance: usize,
+}
+
+impl GridPart1 {
+    pub fn new(m: Vec<Vec<char>>, tolerance: usize) -> Self {
+        let fc = m.iter().fold(0, |acc, line| {
+            acc + line.iter().filter(|&c| *c == '.').count()
+        });
+
+        GridPart1 {
+            m,
+            fc,
+            tolerance,
+        }
+    }
+}
+
+impl Simulation for GridPart1 {
+    fn step(&mut self) {
+        self.single_round();
+    }
+}
+
+impl SeatSimulator for GridPart1 {
+    fn floor_count(&mut self) -> usize {
+        self.fc
+    }
+
+    fn tolerance(&mut self) -> usize {
+        self.tolerance
+    }
+
+    fn change_pos(&mut self, i: usize, j: usize, c: char) {
+        self.m[i][j] = c;
+    }
+
+    fn matrix(&mut self) -> &Vec<Vec<char>> {
+        &self.m
+    }
+
+    fn ahead(&mut self, x: usize, y: usize, _dx: i32, _dy: i32) -> (usize, usize) {
+        (x, y)
+    }
+}
+
+#[derive(Clone,Eq,Hash,PartialEq)]
+pub struct GridPart2 {
+    m: Vec<Vec<char>>,
+    fc: usize,
+    tolerance: usize,
+}
+
+impl GridPart2 {
+    pub fn new(m: Vec<Vec<char>>, tolerance: usize) -> Self {
+        let fc = m.iter().fold(0, |acc, line| {
+            acc + line.iter().filter(|&c| *c == '.').count()
+        });
+
+        GridPart2 {
+            m,
+            fc,
+            tolerance,
+        }
+    }
+}
+
+impl Simulation for GridPart2 {
+    fn step(&mut self) {
+        self.single_round();
+    }
+}
+
+impl SeatSimulator for GridPart2 {
+    fn floor_count(&mut self) -> usize {
+        self.fc
+    }
+
+    fn tolerance(&mut self) -> usize {
+        self.tolerance
+    }
+
+    fn change_pos(&mut self, i: usize, j: usize, c: char) {
+        self.m[i][j] = c;
+    }
+
+    fn matrix(&mut self) -> &Vec<Vec<char>> {
+        &self.m
+    }
+
+    fn ahead(&mut self, row: usize, col: usize, drow: i32, dcol: i32) -> (usize, usize) {
+        let (rows, cols) = (self.matrix().len(), self.matrix()[0].len());
+        let mut i = row as i32;
+        let mut j = col as i32;
+
+        while i >= 0 &&
+            (i as usize) < rows &&
+            j >= 0 &&
+            (j as usize) < cols &&
+            self.matrix()[i as usize][j as usize] == '.' {
+            match (usize::try_from(i+drow), usize::try_from(j+dcol)) {
+                (Ok(i2), Ok(j2)) if i2 < rows && j2 < cols => {
+                    i = i2 as i32;
+                    j = j2 as i32;
+                },
+                _ => break,
+            }
+        }
+
+        (i as usize, j as usize)
+    }
+}
+
+pub fn build_matrix(lines: Vec<String>) -> Vec<Vec<char>> {
+    lines.iter().map(|line| line.chars().collect()).collect()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let matrix = build_matrix(lines.to_vec());
+    let occupied = match part {
+        1 => GridPart1::new(matrix, ADJACENT_TOLERANCE).occupied_seats(&mut Budget::new()),
+        2 => GridPart2::new(matrix, VISIBLE_TOLERANCE).occupied_seats(&mut Budget::new()),
+        _ => return None,
+    };
+
+    Some(occupied.unwrap_or_else(|e| panic!("{}", e)).to_string())
+}
+
+aoc::plugin!(day: 11, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let matrix = build_matrix(
+            vec![
+                "L.LL.LL.LL".to_string(),
+                "LLLLLLL.LL".to_string(),
+                "L.L.L..L..".to_string(),
+                "LLLL.LL.LL".to_string(),
+                "L.LL.LL.LL".to_string(),
+                "L.LLLLL.LL".to_string(),
+                "..L.L.....".to_string(),
+                "LLLLLLLLLL".to_string(),
+                "L.LLLLLL.L".to_string(),
+                "L.LLLLL.LL".to_string(),
+            ],
+        );
+
+        let mut grid1 = GridPart1::new(matrix, ADJACENT_TOLERANCE);
+
+        assert_eq!(
+            "did not converge (ran out of its 2 steps)",
+            grid1.clone().occupied_seats(&mut Budget::with_max_steps(2)).unwrap_err().to_string(),
+        );
+        assert_eq!(Ok(37), grid1.occupied_seats(&mut Budget::unlimited()));
+    }
+
+    #[test]
+    fn test_neighbours_part2() {
+        let matrix = build_matrix(
+            vec![
+                ".......#.".to_string(),
+                "...#.....".to_string(),
+                ".#.......".to_string(),
+                ".........".to_string(),
+                "..#L....#".to_string(),
+                "....#....".to_string(),
+                ".........".to_string(),
+                "#........".to_string(),
+                "...#.....".to_string(),
+            ],
+        );
+
+        let mut grid2 = GridPart2::new(matrix, VISIBLE_TOLERANCE);
+        assert_eq!(8, grid2.occupied_neighbours(4, 3));
+    }
+
+    #[test]
+    fn test_neighbours_part2_empty_list() {
+        let matrix = build_matrix(
+            vec![
+                ".##.##.".to_string(),
+                "#.#.#.#".to_string(),
+                "##...##".to_string(),
+                "...L...".to_string(),
+                "##...##".to_string(),
+                "#.#.#.#".to_string(),
+                ".##.##.".to_string(),
+            ]
+        );
+
+        let mut grid2 = GridPart2::new(matrix, VISIBLE_TOLERANCE);
+        assert_eq!(0, grid2.occupied_neighbours(3, 3));
+    }
+
+    #[test]
+    fn test_part2() {
+        let matrix = build_matrix(
+            vec![
+                "L.LL.LL.LL".to_string(),
+                "LLLLLLL.LL".to_string(),
+                "L.L.L..L..".to_string(),
+                "LLLL.LL.LL".to_string(),
+                "L.LL.LL.LL".to_string(),
+                "L.LLLLL.LL".to_string(),
+                "..L.L.....".to_string(),
+                "LLLLLLLLLL".to_string(),
+                "L.LLLLLL.L".to_string(),
+                "L.LLLLL.LL".to_string(),
+            ],
+        );
+
+        let mut grid2 = GridPart2::new(matrix, VISIBLE_TOLERANCE);
+
+        assert_eq!(Ok(26), grid2.occupied_seats(&mut Budget::unlimited()));
+    }
+}
@@ -0,0 +1,144 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/13
+
+use std::str::FromStr;
+
+use aoc::numeric::{Number, Signed, Wide};
+
+
+pub fn shuttle_search(lines: &[String]) -> usize {
+    let timestamp = usize::from_str(&lines[0]).unwrap();
+    let mut wait_time = usize::MAX;
+    let mut bus_id = 0;
+
+    for id in lines[1].split(',') {
+        if let Ok(n) = usize::from_str(id) {
+            let wt = n * (timestamp / n + 1) - timestamp;
+            if wt < wait_time {
+                wait_time = wt;
+                bus_id = n;
+            }
+        }
+    }
+
+    wait_time * bus_id
+}
+
+// https://math.stackexchange.com/questions/147152/how-to-find-the-meeting-number-of-two-sequences
+// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+// https://www.discoverbits.in/post/extended-euclid-algorithm-for-gcd-in-python/
+fn extended_gcd<N: Number + Signed>(a: N, b: N) -> (N, N, N) {
+    /*
+     * The extended GCD not only returns the gcd(a, b) but also the
+     * coefficients of Bezout's identity, which are integers x and y
+     * such that a*x + b*y = gcd(a,b). This method can be used to find
+     * the solutions to linear Diophantine equations.
+     */
+    if a.is_zero() { (b, N::zero(), N::one()) }
+    else {
+        let (g, m, n) = extended_gcd(b.clone() % a.clone(), a.clone());
+        (g, n - (b / a) * m.clone(), m)
+    }
+}
+
+// https://cronokirby.com/posts/2020/12/chinese-remainder-theorem/
+// Many thanks!!
+fn calculate<N: Number + Signed>(a: (N, N), b: (N, N)) -> (N, N) {
+    let (_, m, n) = extended_gcd(a.0.clone(), b.0.clone());
+    let k = a.1*n*b.0.clone() + b.1*m*a.0.clone();
+    let modulus = a.0 * b.0;
+
+    // Because I'm using signed integers, I need to use mod_floor, or else
+    // the regular % will return negative results at some point.
+    (modulus.clone(), k.mod_floor(&modulus))
+}
+
+fn minimum_timestamp<N: Number + Signed>(ids: Vec<(N, N)>) -> N {
+    let (mut a_i, mut a_j) = ids[0].clone();
+
+    for (i, offset) in ids.iter().skip(1) {
+        let tmp = calculate((a_i, a_j), (i.clone(), offset.clone()));
+        a_i = tmp.0;
+        a_j = tmp.1;
+    }
+
+    a_i % a_j
+}
+
+pub fn first_timestamp<N: Number + Signed>(ids: &str) -> N {
+    minimum_timestamp(
+        ids
+            .split(',')
+            .enumerate()
+            .fold(Vec::new(), |mut acc, (i, id)| {
+                if let Some(n) = N::parse(id) {
+                    acc.push((n, N::from_count(i)));
+                }
+                acc
+            }))
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    match part {
+        1 => Some(shuttle_search(lines).to_string()),
+        2 => Some(first_timestamp::<Wide<i128>>(&lines[1]).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 13, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuttle_search() {
+        assert_eq!(
+            295,
+            shuttle_search(
+                &[
+                    "939".to_string(),
+                    "7,13,x,x,59,x,31,19".to_string(),
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn test_minimum_timestamp() {
+        assert_eq!(
+            1068781,
+            minimum_timestamp::<i128>(vec![(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)]),
+        );
+        assert_eq!(
+            754018,
+            minimum_timestamp::<i128>(vec![(67, 0), (7, 1), (59, 2), (61, 3)]),
+        );
+        assert_eq!(
+            779210,
+            minimum_timestamp::<i128>(vec![(67, 0), (7, 2), (59, 3), (61, 4)]),
+        );
+    }
+}
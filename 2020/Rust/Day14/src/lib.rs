@@ -0,0 +1,224 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/14
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use aoc::bits::Pattern;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+
+pub const BITMASK_SIZE: u32 = 36;
+
+pub trait Emulator {
+    fn bitmask(&mut self) -> &mut Pattern;
+    fn memory(&mut self) -> &mut HashMap<u64, u64>;
+    fn translate_value(&mut self, val: u64) -> u64;
+    fn translate_address(&mut self, addr: u64) -> Vec<u64>;
+
+    fn execute(&mut self, lines: &[String]) -> u64 {
+        for line in lines.iter() {
+            self.parse_instruction(line);
+        }
+
+        self.memory().values().sum()
+    }
+
+    fn replace_bitmask(&mut self, s: &str) {
+        *self.bitmask() = match Pattern::from_str(s) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
+        };
+    }
+
+    fn parse_instruction(&mut self, line: &str) {
+        lazy_static! {
+            static ref MASK: Regex = Regex::new(r"mask = (\w+)").unwrap();
+            static ref APPLY: Regex = Regex::new(r"mem\[(\d+)\] = (\d+)").unwrap();
+        }
+
+        if let Some(c) = MASK.captures(line) {
+            self.replace_bitmask(c.get(1).unwrap().as_str());
+        } else if let Some(c) = APPLY.captures(line) {
+            let address = u64::from_str(c.get(1).unwrap().as_str()).unwrap();
+            let value = u64::from_str(c.get(2).unwrap().as_str()).unwrap();
+            let new_value = self.translate_value(value);
+            let new_addrs = self.translate_address(address);
+
+            new_addrs.iter().for_each(|addr| {
+                self.memory().insert(*addr, new_value);
+            });
+        }
+    }
+}
+
+pub struct EmulatorPart1 {
+    mask: Pattern,
+    mem: HashMap<u64, u64>,
+}
+
+impl EmulatorPart1 {
+    pub fn new(size: u32) -> Self {
+        Self {
+            mask: Pattern::any(size),
+            mem: HashMap::new(),
+        }
+    }
+}
+
+impl Emulator for EmulatorPart1 {
+    fn bitmask(&mut self) -> &mut Pattern {
+        &mut self.mask
+    }
+
+    fn memory(&mut self) -> &mut HashMap<u64, u64> {
+        &mut self.mem
+    }
+
+    fn translate_address(&mut self, addr: u64) -> Vec<u64> {
+        vec![addr]
+    }
+
+    // Bits set to 0 or 1 in the mask overwrite the value, while the X's
+    // leave it unchanged.
+    fn translate_value(&mut self, val: u64) -> u64 {
+        self.bitmask().apply(val)
+    }
+}
+
+pub struct EmulatorPart2 {
+    mask: Pattern,
+    mem: HashMap<u64, u64>,
+}
+
+impl EmulatorPart2 {
+    pub fn new(size: u32) -> Self {
+        Self {
+            mask: Pattern::any(size),
+            mem: HashMap::new(),
+        }
+    }
+}
+
+impl Emulator for EmulatorPart2 {
+    fn bitmask(&mut self) -> &mut Pattern {
+        &mut self.mask
+    }
+
+    fn memory(&mut self) -> &mut HashMap<u64, u64> {
+        &mut self.mem
+    }
+
+    // A 0 in the mask leaves the address bit unchanged, a 1 overwrites it
+    // and an X makes it float, i.e. take all the possible values.
+    fn translate_address(&mut self, addr: u64) -> Vec<u64> {
+        self.bitmask().overlay(addr).expand().collect()
+    }
+
+    fn translate_value(&mut self, val: u64) -> u64 {
+        val
+    }
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    match part {
+        1 => Some(EmulatorPart1::new(BITMASK_SIZE).execute(lines).to_string()),
+        2 => Some(EmulatorPart2::new(BITMASK_SIZE).execute(lines).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 14, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_value() {
+        let mut emul = EmulatorPart1::new(BITMASK_SIZE);
+
+        emul.replace_bitmask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        assert_eq!(73, emul.translate_value(11));
+        assert_eq!(101, emul.translate_value(101));
+        assert_eq!(64, emul.translate_value(0));
+    }
+
+    #[test]
+    fn test_translate_address1() {
+        let mut emul = EmulatorPart2::new(BITMASK_SIZE);
+
+        emul.replace_bitmask("000000000000000000000000000000X1001X");
+        assert_eq!(
+            vec![26, 27, 58, 59],
+            emul.translate_address(42),
+        );
+    }
+
+    #[test]
+    fn test_translate_address2() {
+        let mut emul = EmulatorPart2::new(BITMASK_SIZE);
+
+        emul.replace_bitmask("00000000000000000000000000000000X0XX");
+        assert_eq!(
+            vec![16, 17, 18, 19, 24, 25, 26, 27],
+            emul.translate_address(26),
+        );
+    }
+
+    #[test]
+    fn test_sum_memory1() {
+        let mut emul = EmulatorPart1::new(BITMASK_SIZE);
+
+        assert_eq!(
+            165,
+            emul.execute(
+                &[
+                    "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".to_string(),
+                    "mem[8] = 11".to_string(),
+                    "mem[7] = 101".to_string(),
+                    "mem[8] = 0".to_string(),
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn test_sum_memory2() {
+        let mut emul = EmulatorPart2::new(BITMASK_SIZE);
+
+        assert_eq!(
+            208,
+            emul.execute(
+                &[
+                    "mask = 000000000000000000000000000000X1001X".to_string(),
+                    "mem[42] = 100".to_string(),
+                    "mask = 00000000000000000000000000000000X0XX".to_string(),
+                    "mem[26] = 1".to_string(),
+                ]
+            ),
+        );
+    }
+}
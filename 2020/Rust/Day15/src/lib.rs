@@ -0,0 +1,116 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/15
+
+use std::collections::HashMap;
+
+
+pub const STARTING_NUMBERS: [usize; 6] = [2, 0, 1, 9, 5, 19];
+
+fn init_sequence(seq: &[usize]) -> HashMap<usize, Vec<usize>> {
+    (0..seq.len()).fold(HashMap::new(), |mut acc, i| {
+        acc.entry(seq[i]).or_default().push(i +1);
+        acc
+    })
+}
+
+pub fn nth_number_spoken(numbers: Vec<usize>, k: usize) -> usize {
+    let mut seen: HashMap<usize, Vec<usize>> = init_sequence(&numbers);
+
+    let len = numbers.len();
+    let mut last_number = numbers[len-1];
+    let mut turn = len+1;
+
+    while turn <= k {
+        match seen.get(&last_number) {
+            None => last_number = 0,
+            Some(o) => {
+                let l = o.len();
+                last_number = o[l-1] - o[0.max(l as i32 -2) as usize];
+            },
+        }
+
+        seen.entry(last_number).or_default().push(turn);
+        turn += 1;
+    }
+
+    last_number
+}
+
+// Keeps the last turn in which each number was spoken in a Vec instead. Any
+// number spoken after the starting ones is a difference between two turns,
+// so it's always smaller than k.
+pub fn nth_number_spoken_dense(numbers: &[usize], k: usize) -> usize {
+    let len = numbers.len();
+    let mut last_turn = vec![0_u32; k.max(numbers.iter().max().unwrap() + 1)];
+
+    for (i, n) in numbers[..len-1].iter().enumerate() {
+        last_turn[*n] = (i+1) as u32;
+    }
+
+    let mut last_number = numbers[len-1];
+
+    for turn in len..k {
+        let previous = last_turn[last_number] as usize;
+        last_turn[last_number] = turn as u32;
+        last_number = if previous == 0 { 0 } else { turn - previous };
+    }
+
+    last_number
+}
+
+// The starting numbers are in the code, so there's no input to read
+fn solve(part: u8, _lines: &[String]) -> Option<String> {
+    match part {
+        1 => Some(nth_number_spoken_dense(&STARTING_NUMBERS, 2020).to_string()),
+        2 => Some(nth_number_spoken_dense(&STARTING_NUMBERS, 30000000).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 15, parts: 2, solve: solve, reads_input: false);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nth_number_spoken() {
+        assert_eq!(436,  nth_number_spoken(vec![0, 3, 6], 2020));
+        assert_eq!(1,    nth_number_spoken(vec![1, 3, 2], 2020));
+        assert_eq!(10,   nth_number_spoken(vec![2, 1, 3], 2020));
+        assert_eq!(27,   nth_number_spoken(vec![1, 2, 3], 2020));
+        assert_eq!(78,   nth_number_spoken(vec![2, 3, 1], 2020));
+        assert_eq!(438,  nth_number_spoken(vec![3, 2, 1], 2020));
+        assert_eq!(1836, nth_number_spoken(vec![3, 1, 2], 2020));
+    }
+
+    #[test]
+    fn test_nth_number_spoken_dense() {
+        assert_eq!(0,    nth_number_spoken_dense(&[0, 3, 6], 10));
+        assert_eq!(436,  nth_number_spoken_dense(&[0, 3, 6], 2020));
+        assert_eq!(1,    nth_number_spoken_dense(&[1, 3, 2], 2020));
+        assert_eq!(1836, nth_number_spoken_dense(&[3, 1, 2], 2020));
+        assert_eq!(175594, nth_number_spoken_dense(&[0, 3, 6], 30000000));
+    }
+}
@@ -24,62 +24,50 @@
 
 #![allow(non_snake_case)]
 
-use std::collections::HashMap;
+use aoc::config;
+use aoc::runner::Runner;
+use day15::{nth_number_spoken, nth_number_spoken_dense, STARTING_NUMBERS};
+use serde::Deserialize;
 
 
-fn init_sequence(seq: &Vec<usize>) -> HashMap<usize, Vec<usize>> {
-    (0..seq.len()).fold(HashMap::new(), |mut acc, i| {
-        acc.entry(seq[i]).or_insert(Vec::new()).push(i +1);
-        acc
-    })
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    part1_turns: usize,
+    part2_turns: usize,
 }
 
-fn nth_number_spoken(numbers: Vec<usize>, k: usize) -> usize {
-    let mut seen: HashMap<usize, Vec<usize>> = init_sequence(&numbers);
-
-    let len = numbers.len();
-    let mut last_number = numbers[len-1];
-    let mut turn = len+1;
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            part1_turns: 2020,
+            part2_turns: 30000000,
+        }
+    }
+}
 
-    while turn <= k {
-        match seen.get(&last_number) {
-            None => last_number = 0,
-            Some(o) => {
-                let l = o.len();
-                last_number = o[l-1] - o[0.max(l as i32 -2) as usize];
-            },
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.part1_turns.min(self.part2_turns) < STARTING_NUMBERS.len() {
+            return Err(format!("turns must be at least {}", STARTING_NUMBERS.len()));
+        }
+        if self.part1_turns.max(self.part2_turns) > u32::MAX as usize {
+            return Err(format!("turns can't go over {}", u32::MAX));
         }
 
-        seen.entry(last_number).or_insert(Vec::new()).push(turn);
-        turn += 1;
+        Ok(())
     }
-
-    last_number
 }
 
 fn main() {
-    println!(
-        "Day 15 / Part 1: {}",
-        nth_number_spoken(vec![2, 0, 1, 9, 5, 19], 2020),
-    );
-    println!(
-        "Day 15 / Part 2: {}",
-        nth_number_spoken(vec![2, 0, 1, 9, 5, 19], 30000000),
-    );
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    let mut runner = Runner::new(15);
+    let params: Params = runner.params();
 
-    #[test]
-    fn test_nth_number_spoken() {
-        assert_eq!(436,  nth_number_spoken(vec![0, 3, 6], 2020));
-        assert_eq!(1,    nth_number_spoken(vec![1, 3, 2], 2020));
-        assert_eq!(10,   nth_number_spoken(vec![2, 1, 3], 2020));
-        assert_eq!(27,   nth_number_spoken(vec![1, 2, 3], 2020));
-        assert_eq!(78,   nth_number_spoken(vec![2, 3, 1], 2020));
-        assert_eq!(438,  nth_number_spoken(vec![3, 2, 1], 2020));
-        assert_eq!(1836, nth_number_spoken(vec![3, 1, 2], 2020));
+    for (part, turns) in [(1, params.part1_turns), (2, params.part2_turns)] {
+        runner.variants(part)
+            .variant("hashmap", || nth_number_spoken(STARTING_NUMBERS.to_vec(), turns))
+            .variant("dense", || nth_number_spoken_dense(&STARTING_NUMBERS, turns))
+            .run();
     }
+    runner.finish();
 }
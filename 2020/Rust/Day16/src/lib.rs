@@ -0,0 +1,310 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/16
+
+use std::collections::{HashMap,HashSet};
+use std::str::FromStr;
+
+use aoc::budget::{Budget, DidNotConverge};
+use aoc::explain;
+use aoc::interval::IntervalSet;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+
+pub type FieldsRanges = HashMap<String, IntervalSet<usize>>;
+pub type Ticket = Vec<usize>;
+
+fn parse_ranges(group: &[String]) -> FieldsRanges {
+    lazy_static! {
+        static ref RULE: Regex = Regex::new(r"(.*): (\d+)\-(\d+) or (\d+)\-(\d+)").unwrap();
+    }
+
+    let mut ranges = HashMap::new();
+
+    for line in group.iter() {
+        match RULE.captures(line) {
+            Some(g) => {
+                let bound = |i| usize::from_str(g.get(i).unwrap().as_str()).unwrap();
+                ranges.insert(
+                    g.get(1).unwrap().as_str().to_owned(),
+                    vec![(bound(2), bound(3)), (bound(4), bound(5))].into_iter().collect(),
+                );
+            },
+            None => panic!("Malformed rule"),
+        }
+    }
+
+    ranges
+}
+
+fn parse_other_tickets(group: &[String]) -> Vec<Ticket> {
+    group[1..]
+        .iter()
+        .map(|ticket|
+             ticket
+             .split(',')
+             .map(|n| usize::from_str(n).unwrap())
+             .collect()
+        )
+        .collect()
+}
+
+// Values that are valid for at least one field
+fn any_field(ranges: &FieldsRanges) -> IntervalSet<usize> {
+    ranges.values().fold(IntervalSet::new(), |acc, r| acc.union(r))
+}
+
+fn ticket_invalid_values(ticket: &Ticket, valid: &IntervalSet<usize>) -> usize {
+    ticket
+        .iter()
+        .filter(|&field| !valid.contains(*field))
+        .sum()
+}
+
+pub fn scanning_error_rate(other_tickets: &[Ticket], ranges: &FieldsRanges) -> usize {
+    let valid = any_field(ranges);
+
+    other_tickets
+        .iter()
+        .map(|ticket| ticket_invalid_values(ticket, &valid))
+        .sum()
+}
+
+fn is_valid_ticket(ticket: &Ticket, valid: &IntervalSet<usize>) -> bool {
+    ticket.iter().all(|field| valid.contains(*field))
+}
+
+fn valid_tickets(other_tickets: &[Ticket], ranges: &FieldsRanges) -> Vec<Ticket> {
+    let valid = any_field(ranges);
+
+    other_tickets
+        .iter()
+        .filter(|&t| is_valid_ticket(t, &valid))
+        .cloned()
+        .collect()
+}
+
+fn candidate_indices(
+    tickets: &[Ticket],
+    ranges: &FieldsRanges
+) -> HashMap<String, HashSet<usize>> {
+    let num_fields = tickets[0].len();
+    let mut res = HashMap::new();
+
+    for (field, r) in ranges.iter() {
+        for i in 0..num_fields {
+            if (0..tickets.len()).all(|j| r.contains(tickets[j][i])) {
+                res.entry(field.to_string()).or_insert_with(HashSet::new).insert(i);
+            }
+        }
+    }
+
+    res
+}
+
+// Fields already matched to a column, then the ones still undecided.
+fn elimination_state(
+    assured: &HashMap<String, usize>,
+    candidates: &HashMap<String, HashSet<usize>>,
+) -> String {
+    let resolved = assured
+        .iter()
+        .sorted()
+        .map(|(name, index)| format!("{} = {}", name, index))
+        .join(", ");
+    let undecided = candidates
+        .iter()
+        .sorted_by(|a, b| a.0.cmp(b.0))
+        .map(|(name, indices)| {
+            format!("{} is one of {:?}", name, indices.iter().sorted().collect::<Vec<_>>())
+        })
+        .join("\n");
+
+    format!("resolved: {}\n{}", resolved, undecided)
+}
+
+fn fields_indices(
+    tickets: &[Ticket],
+    ranges: &FieldsRanges,
+    budget: &mut Budget,
+) -> Result<HashMap<String, usize>, DidNotConverge> {
+    let mut candidates = candidate_indices(tickets, ranges);
+    let mut assured: HashMap<String, usize> = candidates
+        .iter()
+        .filter(|(_, v)| v.len() == 1)
+        .fold(HashMap::new(), |mut acc, (k, v)| {
+            acc.insert(k.to_string(), *v.iter().next().unwrap());
+            acc
+        });
+
+    assured.iter().for_each(|(name, index)| {
+        explain!("elimination", "round 0: {} can only be column {}", name, index);
+        candidates.remove(name);
+    });
+
+    let mut round = 0;
+    while assured.len() < tickets[0].len() {
+        if let Err(e) = budget.tick() {
+            return Err(e.with_state(elimination_state(&assured, &candidates)));
+        }
+
+        round += 1;
+        let assured_values: Vec<usize> = assured.values().cloned().collect();
+        let mut to_remove: Vec<String> = Vec::new();
+
+        for v in assured_values.iter() {
+            for (u_k, u_v) in candidates.iter_mut() {
+                if u_v.remove(v) && u_v.len() == 1 {
+                    let index = *u_v.iter().next().unwrap();
+                    explain!(
+                        "elimination", "round {}: {} can only be column {}",
+                        round, u_k, index,
+                    );
+                    assured.insert(u_k.to_string(), index);
+                    to_remove.push(u_k.to_string());
+                }
+            }
+        }
+
+        if to_remove.is_empty() {
+            return Err(DidNotConverge::stalled(elimination_state(&assured, &candidates)));
+        }
+
+        to_remove.iter().for_each(|name| { candidates.remove(name); });
+    }
+
+    Ok(assured)
+}
+
+pub fn destination_fields_value(
+    your_ticket: &Ticket,
+    other_tickets: &[Ticket],
+    ranges: &FieldsRanges,
+    prefix: &str,
+) -> Result<usize, DidNotConverge> {
+    let tickets = valid_tickets(other_tickets, ranges);
+    let fi = fields_indices(&tickets, ranges, &mut Budget::new())?;
+
+    let mut res = 1;
+    for (field, index) in fi.iter() {
+        if field.starts_with(prefix) {
+            res *= your_ticket[*index];
+        }
+    }
+
+    Ok(res)
+}
+
+/// The rules for the ticket fields, your ticket and the nearby tickets.
+pub fn parse_notes(lines: Vec<String>) -> (FieldsRanges, Ticket, Vec<Ticket>) {
+    let groups = &lines.into_iter().group_by(|line| !line.is_empty());
+    let grouped: Vec<Vec<String>> = groups
+        .into_iter()
+        .map(|(_, group)| group.collect::<Vec<String>>())
+        .filter(|g| !g[0].is_empty())
+        .collect();
+
+    let ranges = parse_ranges(&grouped[0]);
+    let your_ticket: Ticket = grouped[1][1]
+        .split(',')
+        .map(|n| usize::from_str(n).unwrap())
+        .collect();
+    let other_tickets = parse_other_tickets(&grouped[2]);
+
+    (ranges, your_ticket, other_tickets)
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let (ranges, your_ticket, other_tickets) = parse_notes(lines.to_vec());
+
+    let answer = match part {
+        1 => Ok(scanning_error_rate(&other_tickets, &ranges)),
+        2 => destination_fields_value(&your_ticket, &other_tickets, &ranges, "departure"),
+        _ => return None,
+    };
+
+    Some(answer.unwrap_or_else(|e| panic!("{}", e)).to_string())
+}
+
+aoc::plugin!(day: 16, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(fields: &[(&str, &[(usize, usize)])]) -> FieldsRanges {
+        fields
+            .iter()
+            .map(|(name, r)| (name.to_string(), r.iter().copied().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_tickets() {
+        let other_tickets = vec![
+            vec![7, 3, 47],
+            vec![40, 4, 50],
+            vec![55, 2, 20],
+            vec![38, 6, 12],
+        ];
+        let ranges = ranges(&[
+            ("class", &[(1,3), (5,7)]),
+            ("row", &[(6,11), (33,44)]),
+            ("seat", &[(13,40), (45,50)]),
+        ]);
+
+        assert_eq!(
+            vec![vec![7, 3, 47]],
+            valid_tickets(&other_tickets, &ranges),
+        );
+        assert_eq!(71, scanning_error_rate(&other_tickets, &ranges));
+    }
+
+    #[test]
+    fn test_fields_indices() {
+        let tickets = vec![vec![3, 9, 18], vec![15, 1, 5], vec![5, 14, 9]];
+        let fields = ranges(&[
+            ("class", &[(0,1), (4,19)]),
+            ("row", &[(0,5), (8,19)]),
+            ("seat", &[(0,13), (16,19)]),
+        ]);
+
+        let fi = fields_indices(&tickets, &fields, &mut Budget::unlimited()).unwrap();
+        assert_eq!(Some(&1), fi.get("class"));
+        assert_eq!(Some(&0), fi.get("row"));
+        assert_eq!(Some(&2), fi.get("seat"));
+
+        // Both fields fit both columns, so there's no way to tell them apart
+        let tickets = vec![vec![1, 2]];
+        let fields = ranges(&[("class", &[(0,5)]), ("row", &[(0,5)])]);
+
+        let e = fields_indices(&tickets, &fields, &mut Budget::unlimited()).unwrap_err();
+        assert_eq!("did not converge (stopped making progress)", e.to_string());
+        assert_eq!("resolved: \nclass is one of [0, 1]\nrow is one of [0, 1]", e.state);
+
+        let e = fields_indices(&tickets, &fields, &mut Budget::with_max_steps(0)).unwrap_err();
+        assert_eq!("did not converge (ran out of its 0 steps)", e.to_string());
+    }
+}
@@ -0,0 +1,223 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/17
+
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use aoc::sim::Simulation;
+use itertools::iproduct;
+
+
+const INPUT: [[char; 8]; 8] = [
+    ['#','.','#','#','#','#','#','.'],
+    ['#','.','.','#','#','.','.','.'],
+    ['.','#','#','.','.','#','.','.'],
+    ['#','.','#','#','.','#','#','#'],
+    ['.','#','.','#','.','#','.','.'],
+    ['#','.','#','#','.','.','#','.'],
+    ['#','#','#','#','#','.','.','#'],
+    ['.','.','#','.','#','.','#','#'],
+];
+
+pub trait Coordinate {
+    type Item;
+
+    fn neighbours(&self) -> Vec<Self::Item>;
+    fn new(x: i32, y: i32) -> Self::Item;
+}
+
+#[derive(Clone,Copy,Eq,Hash,PartialEq)]
+pub struct Coord3D(i32,i32,i32);
+
+impl Coordinate for Coord3D {
+    type Item = Coord3D;
+
+    fn neighbours(&self) -> Vec<Self::Item> {
+        let Coord3D(x,y,z) = &self;
+
+        iproduct!(-1..=1,-1..=1,-1..=1)
+            .filter(|&(dx,dy,dz)| !(dx == 0 && dy == 0 && dz == 0))
+            .fold(Vec::new(), |mut acc, (dx, dy, dz)| {
+                acc.push(Coord3D(x+dx, y+dy, z+dz));
+                acc
+            })
+    }
+
+    fn new(x: i32, y: i32) -> Self::Item {
+        Coord3D(x, y, 0)
+    }
+}
+
+#[derive(Clone,Copy,Eq,Hash,PartialEq)]
+pub struct Coord4D(i32,i32,i32,i32);
+
+impl Coordinate for Coord4D {
+    type Item = Coord4D;
+
+    fn neighbours(&self) -> Vec<Self::Item> {
+        let Coord4D(x,y,z,w) = &self;
+
+        iproduct!(-1..=1,-1..=1,-1..=1,-1..=1)
+            .filter(|&(dx,dy,dz,dw)| !(dx == 0 && dy == 0 && dz == 0 && dw == 0))
+            .fold(Vec::new(), |mut acc, (dx, dy, dz, dw)| {
+                acc.push(Coord4D(x+dx, y+dy, z+dz, w+dw));
+                acc
+            })
+    }
+
+    fn new(x: i32, y: i32) -> Self::Item {
+        Coord4D(x, y, 0, 0)
+    }
+}
+
+fn run_cycle<T>(active: &HashSet<T>) -> HashSet<T>
+where
+    T: Coordinate<Item = T> + Copy + Clone + Eq + Hash
+{
+    let mut new_active = HashSet::new();
+    let to_visit: HashSet<T> = active
+        .iter()
+        .flat_map(|coord| coord.neighbours())
+        .collect();
+
+    for coord in to_visit.iter() {
+        let n: Vec<T> = coord.neighbours()
+            .iter()
+            .cloned()
+            .filter(|c| active.contains(c))
+            .collect();
+
+        if n.len() == 3 || (active.contains(coord) && n.len() == 2) {
+            new_active.insert(*coord);
+        }
+    }
+
+    new_active
+}
+
+// The active cubes of the pocket dimension.
+#[derive(Clone,Eq,PartialEq)]
+struct Pocket<T: Eq + Hash>(HashSet<T>);
+
+// HashSet doesn't implement Hash, because its iteration order isn't
+// fixed. Adding up the hashes of the cubes gives the same result in
+// whatever order they come.
+impl<T: Eq + Hash> Hash for Pocket<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let sum = self.0.iter().fold(0_u64, |acc, cube| {
+            let mut hasher = DefaultHasher::new();
+            cube.hash(&mut hasher);
+            acc.wrapping_add(hasher.finish())
+        });
+
+        self.0.len().hash(state);
+        sum.hash(state);
+    }
+}
+
+impl<T> Simulation for Pocket<T>
+where
+    T: Coordinate<Item = T> + Copy + Eq + Hash
+{
+    fn step(&mut self) {
+        self.0 = run_cycle(&self.0);
+    }
+}
+
+fn init_active_cubes<T>(matrix: &[Vec<char>]) -> HashSet<T>
+where
+    T: Coordinate<Item = T> + Eq + Hash 
+{
+    (0..matrix[0].len())
+        .flat_map(|x| (0..matrix.len()).map(move |y| (x, y)))
+        .filter(|(x, y)| matrix[*y][*x] == '#')
+        .fold(HashSet::new(), |mut acc, (x, y)| {
+            acc.insert(T::new(x as i32, y as i32));
+            acc
+        })
+}
+
+pub fn active_cubes<T>(matrix: &[Vec<char>], cycles: usize) -> usize
+where
+    T: Coordinate<Item = T> + Copy + Eq + Hash
+{
+    let pocket = Pocket(init_active_cubes::<T>(matrix));
+
+    pocket.after(cycles).0.len()
+}
+
+pub fn initial_state() -> Vec<Vec<char>> {
+    INPUT
+        .to_vec()
+        .iter()
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+// The initial state is in the code, so there's no input to read
+fn solve(part: u8, _lines: &[String]) -> Option<String> {
+    match part {
+        1 => Some(active_cubes::<Coord3D>(&initial_state(), 6).to_string()),
+        2 => Some(active_cubes::<Coord4D>(&initial_state(), 6).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 17, parts: 2, solve: solve, reads_input: false);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_cubes_no_cycles() {
+        assert_eq!(
+            112,
+            active_cubes::<Coord3D>(
+                &[
+                    vec!['.', '#', '.'],
+                    vec!['.', '.', '#'],
+                    vec!['#', '#', '#'],
+                ],
+                6,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_active_cubes_6_cycles() {
+        assert_eq!(
+            848,
+            active_cubes::<Coord4D>(
+                &[
+                    vec!['.', '#', '.'],
+                    vec!['.', '.', '#'],
+                    vec!['#', '#', '#'],
+                ],
+                6,
+            ),
+        );
+    }
+}
@@ -0,0 +1,259 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/18
+
+#![allow(non_snake_case)]
+
+use aoc::numeric::{Number, Wide};
+
+
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum Token<N> {
+    Number(N),
+    Add,
+    Mul,
+    LeftParen,
+    RightParen,
+}
+
+pub type Expression<N> = Vec<Token<N>>;
+
+pub trait Calculator {
+    fn eval<N: Number>(exp: &Expression<N>) -> Token<N>;
+
+    fn calc<N: Number>(v: &mut Vec<Token<N>>) {
+        if v.len() < 3 { return }
+
+        use Token::*;
+
+        match (v.pop(), v.pop(), v.pop()) {
+            (Some(Number(x)), Some(Add), Some(Number(y))) => v.push(Number(x+y)),
+            (Some(Number(x)), Some(Mul), Some(Number(y))) => v.push(Number(x*y)),
+            _ => panic!("wtf"),
+        }
+    }
+}
+
+pub struct BasicCalculator;
+
+impl Calculator for BasicCalculator {
+    fn eval<N: Number>(exp: &Expression<N>) -> Token<N> {
+        let mut stacks: Vec<Vec<Token<N>>> = vec![vec![]];
+        let mut i = 0;
+
+        for token in exp.iter() {
+            match token {
+                Token::Add | Token::Mul => stacks[i].push(token.clone()),
+                Token::LeftParen => { stacks.push(Vec::new()); i += 1; },
+                Token::RightParen => {
+                    if let Some(n) = stacks[i].pop() {
+                        stacks.pop();
+                        i -= 1;
+                        stacks[i].push(n);
+                        Self::calc(&mut stacks[i]);
+                    }
+                },
+                Token::Number(_) => {
+                    stacks[i].push(token.clone());
+                    Self::calc(&mut stacks[i]);
+                },
+            }
+        }
+
+        stacks[0].swap_remove(0)
+    }
+}
+
+pub struct AdvancedCalculator;
+
+impl Calculator for AdvancedCalculator {
+    fn eval<N: Number>(exp: &Expression<N>) -> Token<N> {
+        fn _calcWhile<N: Number>(v: &mut Vec<Token<N>>) {
+            while v.len() > 1 {
+                AdvancedCalculator::calc(v);
+            }
+        }
+
+        fn _calcWhileAdd<N: Number>(v: &mut Vec<Token<N>>) {
+            while v.len() > 1 &&
+                v[v.len() - 2] == Token::Add {
+                AdvancedCalculator::calc(v);
+            }
+        }
+
+        let mut stacks: Vec<Vec<Token<N>>> = vec![vec![]];
+        let mut i = 0;
+
+        for token in exp.iter() {
+            match token {
+                Token::Add | Token::Mul => stacks[i].push(token.clone()),
+                Token::LeftParen => { stacks.push(Vec::new()); i += 1; },
+                Token::RightParen => {
+                    _calcWhile(&mut stacks[i]);
+                    if let Some(n) = stacks[i].pop() {
+                        stacks.pop();
+                        i -= 1;
+                        stacks[i].push(n);
+                        _calcWhileAdd(&mut stacks[i]);
+                    }
+                },
+                Token::Number(_) => {
+                    stacks[i].push(token.clone());
+                    _calcWhileAdd(&mut stacks[i]);
+                },
+            }
+        }
+
+        _calcWhile(&mut stacks[0]);
+
+        stacks[0].swap_remove(0)
+    }
+}
+
+fn parse<N: Number>(exp: &str) -> Expression<N> {
+    let len = exp.len();
+    let t = exp.chars().collect::<Vec<char>>();
+    let mut i = 0;
+    let mut res = Vec::new();
+
+    while i < len {
+        match t[i] {
+            ' ' => (),
+            '+' => res.push(Token::Add),
+            '*' => res.push(Token::Mul),
+            '(' => res.push(Token::LeftParen),
+            ')' => res.push(Token::RightParen),
+            _ => {
+                let mut s = String::new();
+                s.push(t[i]);
+                while i < len-1 && t[i+1].is_ascii_digit() {
+                    i += 1;
+                    s.push(t[i]);
+                }
+
+                res.push(Token::Number(N::parse(&s).unwrap()));
+            },
+        }
+
+        i += 1;
+    }
+
+    res
+}
+
+pub fn calculate<T, N>(lines: &[String]) -> N
+where
+    T: Calculator,
+    N: Number,
+{
+    lines
+        .iter()
+        .fold(N::zero(), |acc, line| {
+            if let Token::Number(x) = T::eval(&parse(line)) {
+                acc + x
+            } else {
+                panic!("wtf")
+            }
+        })
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    match part {
+        1 => Some(calculate::<BasicCalculator, Wide<i64>>(lines).to_string()),
+        2 => Some(calculate::<AdvancedCalculator, Wide<i64>>(lines).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 18, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Token::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            vec![
+                Token::Number(1),
+                Token::Add,
+                Token::LeftParen,
+                Token::Number(2),
+                Token::Mul,
+                Token::Number(3),
+                Token::RightParen,
+            ],
+            parse::<i64>("1 + (2 * 3)"),
+        );
+
+        assert_eq!(
+            vec![
+                Token::LeftParen,
+                Token::LeftParen,
+                Token::Number(1),
+                Token::Add,
+                Token::Number(2),
+                Token::RightParen,
+                Token::RightParen,
+            ],
+            parse::<i64>("(    ( 1       +  2  )   )"),
+        );
+    }
+
+    #[test]
+    fn test_eval_basic() {
+        let exp = parse::<i64>("1 + 2 * 3");
+        assert_eq!(Number(9), BasicCalculator::eval(&exp));
+
+        let exp = parse::<i64>("2 * 3 + (4 * 5)");
+        assert_eq!(Number(26), BasicCalculator::eval(&exp));
+
+        let exp = parse::<i64>("5 + (8 * 3 + 9 + 3 * 4 * 3)");
+        assert_eq!(Number(437), BasicCalculator::eval(&exp));
+    }
+
+    #[test]
+    fn test_eval_advanced() {
+        let exp = parse::<i64>("1 + (2 * 3) + (4 * (5 + 6))");
+        assert_eq!(Number(51), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("2 * 3 + (4 * 5)");
+        assert_eq!(Number(46), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("5 + (8 * 3 + 9 + 3 * 4 * 3)");
+        assert_eq!(Number(1445), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))");
+        assert_eq!(Number(669060), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2");
+        assert_eq!(Number(23340), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("((((2 * 3) + 4) * 5) + 6)");
+        assert_eq!(Number(56), AdvancedCalculator::eval(&exp));
+
+        let exp = parse::<i64>("(2 * (3 + (4 * (5 + 6))))");
+        assert_eq!(Number(94), AdvancedCalculator::eval(&exp));
+    }
+}
@@ -0,0 +1,395 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/19
+
+use std::collections::{HashMap,HashSet};
+use std::str::FromStr;
+
+use aoc::budget::{Budget, DidNotConverge};
+use itertools::Itertools;
+
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub enum Rule {
+    Branch(usize),
+    Leaf(char),
+}
+
+pub type Grammar = HashMap<Rule, Vec<Vec<Rule>>>;
+
+// Keeps the matching from running forever. Every expansion of a rule is paid
+// for with the budget, and a rule that is expanded again at the same position
+// while it's still being matched there is left-recursive.
+pub struct Guard {
+    budget: Budget,
+    active: HashSet<(Rule, usize)>,
+}
+
+impl Guard {
+    fn new(budget: Budget) -> Self {
+        Guard { budget, active: HashSet::new() }
+    }
+
+    // Starts expanding rule n at position i of s.
+    fn enter(&mut self, s: &str, i: usize, n: usize) -> Result<(), DidNotConverge> {
+        let at = || format!("matching {} at position {}", s, i);
+
+        if let Err(e) = self.budget.tick() {
+            return Err(e.with_state(at()));
+        }
+        if !self.active.insert((Rule::Branch(n), i)) {
+            return Err(DidNotConverge {
+                reason: format!("rule {} is left-recursive", n),
+                state: at(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn leave(&mut self, i: usize, n: usize) {
+        self.active.remove(&(Rule::Branch(n), i));
+    }
+}
+
+fn match_choices(
+    s: &str,
+    i: usize,
+    choices: &[Rule],
+    g: &Grammar,
+    guard: &mut Guard,
+) -> Result<Option<usize>, DidNotConverge> {
+    let mut j = i;
+
+    for rule in choices.iter() {
+        match match_rule(s, j, *rule, g, guard)? {
+            Some(x) => j = x,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(j))
+}
+
+fn match_branches(
+    s: &str,
+    i: usize,
+    rules: &[Vec<Rule>],
+    g: &Grammar,
+    guard: &mut Guard,
+) -> Result<Option<usize>, DidNotConverge> {
+    let mut best: Option<usize> = None;
+
+    for choices in rules.iter() {
+        if let Some(j) = match_choices(s, i, choices, g, guard)? {
+            best = Some(best.map_or(j, |b| b.min(j)));
+        }
+    }
+
+    Ok(best)
+}
+
+fn match_rule(
+    s: &str,
+    i: usize,
+    rule: Rule,
+    g: &Grammar,
+    guard: &mut Guard,
+) -> Result<Option<usize>, DidNotConverge> {
+    if i >= s.len() { return Ok(None); }
+
+    match rule {
+        Rule::Leaf(c) => Ok(if c == s.chars().nth(i).unwrap() {
+            Some(i+1)
+        } else {
+            None
+        }),
+        Rule::Branch(n) => {
+            guard.enter(s, i, n)?;
+            let res = match_branches(s, i, g.get(&rule).unwrap(), g, guard);
+            guard.leave(i, n);
+            res
+        },
+    }
+}
+
+// Backtracking only keeps the shortest match of each rule, so it misses the
+// messages where a looping rule (e.g. `8: 42 | 42 8`) must match more.
+pub fn valid_string(s: &str, g: &Grammar, guard: &mut Guard) -> Result<bool, DidNotConverge> {
+    match match_rule(s, 0, Rule::Branch(0), g, guard)? {
+        Some(i) => Ok(i == s.len()),
+        None => Ok(false),
+    }
+}
+
+fn build_grammar(lines: &[String]) -> Grammar {
+    lines
+        .iter()
+        .fold(HashMap::new(), |mut acc, line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            let branch = Rule::Branch(usize::from_str(parts[0]).unwrap());
+            let choices = parts[1]
+                .split('|')
+                .map(|p| p
+                     .trim()
+                     .split(' ')
+                     .map(|y| {
+                         let x = y.trim_matches('"');
+                         match usize::from_str(x) {
+                            Ok(n) => Rule::Branch(n),
+                            Err(_) => Rule::Leaf(x.chars().next().unwrap()),
+                         }
+                     })
+                     .collect::<Vec<Rule>>()
+                )
+                .collect::<Vec<Vec<Rule>>>();
+
+            acc.insert(branch, choices);
+            acc
+        })
+}
+
+// Every position where a match of the rule starting at position i may end,
+// for each rule and position seen so far.
+type Table = HashMap<(Rule, usize), Vec<usize>>;
+
+// Unlike backtracking, which settles for the first way a rule matches, this
+// keeps all of them, so a rule can be followed by whatever its shorter and
+// longer matches leave behind.
+fn match_ends(
+    s: &str,
+    i: usize,
+    rule: Rule,
+    g: &Grammar,
+    table: &mut Table,
+    guard: &mut Guard,
+) -> Result<Vec<usize>, DidNotConverge> {
+    if i >= s.len() { return Ok(Vec::new()); }
+
+    let n = match rule {
+        Rule::Leaf(c) => return Ok(if s.as_bytes()[i] as char == c { vec![i+1] } else { Vec::new() }),
+        Rule::Branch(n) => n,
+    };
+
+    if let Some(ends) = table.get(&(rule, i)) {
+        return Ok(ends.clone());
+    }
+
+    guard.enter(s, i, n)?;
+
+    let mut ends = Vec::new();
+    for choices in g.get(&rule).unwrap().iter() {
+        let mut positions = vec![i];
+
+        for r in choices.iter() {
+            let mut next = Vec::new();
+            for p in positions {
+                next.extend(match_ends(s, p, *r, g, table, guard)?);
+            }
+
+            next.sort_unstable();
+            next.dedup();
+            positions = next;
+        }
+
+        ends.extend(positions);
+    }
+
+    ends.sort_unstable();
+    ends.dedup();
+
+    guard.leave(i, n);
+    table.insert((rule, i), ends.clone());
+
+    Ok(ends)
+}
+
+pub fn valid_string_table(s: &str, g: &Grammar, guard: &mut Guard) -> Result<bool, DidNotConverge> {
+    let ends = match_ends(s, 0, Rule::Branch(0), g, &mut HashMap::new(), guard)?;
+    Ok(ends.contains(&s.len()))
+}
+
+pub type Validator = fn(&str, &Grammar, &mut Guard) -> Result<bool, DidNotConverge>;
+
+pub fn total_valid(
+    lines: &[String],
+    g: &Grammar,
+    valid: Validator,
+    budget: Budget,
+) -> Result<usize, DidNotConverge> {
+    let mut guard = Guard::new(budget);
+    let mut total = 0;
+
+    for (n, line) in lines.iter().enumerate() {
+        match valid(line, g, &mut guard) {
+            Ok(valid) => if valid { total += 1; },
+            Err(e) => return Err(DidNotConverge {
+                state: format!("{} of {} messages were valid so far\n{}", total, n, e.state),
+                ..e
+            }),
+        }
+    }
+
+    Ok(total)
+}
+
+/// The grammar and the messages to check against it.
+pub fn parse_input(lines: Vec<String>) -> (Grammar, Vec<String>) {
+    let groups = &lines.into_iter().group_by(|line| !line.is_empty());
+    let mut grouped: Vec<Vec<String>> = groups
+        .into_iter()
+        .map(|(_, group)| group.collect::<Vec<String>>())
+        .filter(|g| !g[0].is_empty())
+        .collect();
+
+    (build_grammar(&grouped[0]), grouped.swap_remove(1))
+}
+
+// Part 2 replaces rules 8 and 11 with ones that loop
+fn with_loops(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| match line.split(':').next() {
+            Some("8") => "8: 42 | 42 8".to_string(),
+            Some("11") => "11: 42 31 | 42 11 31".to_string(),
+            _ => line.clone(),
+        })
+        .collect()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let (grammar, messages) = match part {
+        1 => parse_input(lines.to_vec()),
+        2 => parse_input(with_loops(lines)),
+        _ => return None,
+    };
+    let total = total_valid(&messages, &grammar, valid_string_table, Budget::new());
+
+    Some(total.unwrap_or_else(|e| panic!("{}", e)).to_string())
+}
+
+aoc::plugin!(day: 19, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> Guard {
+        Guard::new(Budget::unlimited())
+    }
+
+    fn valid(s: &str, g: &Grammar) -> bool {
+        valid_string(s, g, &mut guard()).unwrap()
+    }
+
+    #[test]
+    fn test_match_rule() {
+        let grammar = build_grammar(
+            &[
+                "0: 1 2".to_string(),
+                "1: \"a\"".to_string(),
+                "2: \"b\"".to_string(),
+            ],
+        );
+
+        assert_eq!(Ok(Some(1)), match_rule("ab", 0, Rule::Leaf('a'), &grammar, &mut guard()));
+        assert_eq!(Ok(None), match_rule("ab", 0, Rule::Leaf('b'), &grammar, &mut guard()));
+        assert_eq!(Ok(Some(2)), match_rule("ab", 1, Rule::Leaf('b'), &grammar, &mut guard()));
+    }
+
+    #[test]
+    fn test_valid_string_basic() {
+        let grammar = build_grammar(
+            &[
+                "0: 1 2".to_string(),
+                "1: \"a\"".to_string(),
+                "2: 1 3 | 3 1".to_string(),
+                "3: \"b\"".to_string(),
+            ],
+        );
+
+        assert!(valid("aab", &grammar));
+        assert!(valid("aba", &grammar));
+        assert!(!valid("bbb", &grammar));
+    }
+
+    #[test]
+    fn test_valid_string_basic2() {
+        let grammar = build_grammar(
+            &[
+                "0: 4 1 5".to_string(),
+                "1: 2 3 | 3 2".to_string(),
+                "2: 4 4 | 5 5".to_string(),
+                "3: 4 5 | 5 4".to_string(),
+                "4: \"a\"".to_string(),
+                "5: \"b\"".to_string(),
+            ],
+        );
+
+        assert!(valid("ababbb", &grammar));
+        assert!(!valid("bababa", &grammar));
+        assert!(valid("abbbab", &grammar));
+        assert!(!valid("aaabbb", &grammar));
+        assert!(!valid("aaaabbb", &grammar));
+    }
+
+    #[test]
+    fn test_left_recursion() {
+        let grammar = build_grammar(
+            &[
+                "0: 3 | 1 2".to_string(),
+                "1: \"a\"".to_string(),
+                "2: 2 1 | \"b\"".to_string(),
+                "3: \"b\"".to_string(),
+            ],
+        );
+        let lines = vec!["b".to_string(), "ab".to_string()];
+
+        assert!(valid("b", &grammar));
+
+        let e = total_valid(&lines, &grammar, valid_string, Budget::unlimited()).unwrap_err();
+        assert_eq!("did not converge (rule 2 is left-recursive)", e.to_string());
+        assert_eq!("1 of 1 messages were valid so far\nmatching ab at position 1", e.state);
+
+        let e = total_valid(&lines, &grammar, valid_string, Budget::with_max_steps(3)).unwrap_err();
+        assert_eq!("did not converge (ran out of its 3 steps)", e.to_string());
+
+        let e = total_valid(&lines, &grammar, valid_string_table, Budget::unlimited()).unwrap_err();
+        assert_eq!("did not converge (rule 2 is left-recursive)", e.to_string());
+    }
+
+    #[test]
+    fn test_table_keeps_every_match() {
+        let grammar = build_grammar(
+            &[
+                "0: 8 1".to_string(),
+                "1: \"a\"".to_string(),
+                "8: 1 | 1 8".to_string(),
+            ],
+        );
+
+        assert!(valid_string_table("aaa", &grammar, &mut guard()).unwrap());
+        assert!(!valid_string("aaa", &grammar, &mut guard()).unwrap());
+        assert!(!valid_string_table("a", &grammar, &mut guard()).unwrap());
+    }
+}
@@ -24,185 +24,36 @@
 
 #![allow(non_snake_case)]
 
-use std::collections::HashMap;
-use std::str::FromStr;
-
+use aoc::budget::Budget;
 use aoc::fs::get_file_contents;
-use itertools::Itertools;
-
-
-#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
-enum Rule {
-    Branch(usize),
-    Leaf(char),
-}
-
-type Grammar = HashMap<Rule, Vec<Vec<Rule>>>;
-
-fn match_choices(s: &str, i: usize, choices: &Vec<Rule>, g: &Grammar) -> Option<usize> {
-    let mut j = i;
-
-    for rule in choices.iter() {
-        match match_rule(s, j, *rule, g) {
-            Some(x) => j = x,
-            None => return None,
-        }
-    }
-
-    Some(j)
-}
-
-fn match_branches(s: &str, i: usize, rules: &Vec<Vec<Rule>>, g: &Grammar) -> Option<usize> {
-    rules
-        .iter()
-        .map(|choices| match_choices(s, i, choices, g))
-        .filter(|res| res.is_some())
-        .map(|res| res.unwrap())
-        .min()
-}
-
-fn match_rule(s: &str, i: usize, rule: Rule, g: &Grammar) -> Option<usize> {
-    if i >= s.len() { None }
-    else {
-        match rule {
-            Rule::Leaf(c) => if c == s.chars().nth(i).unwrap() {
-                Some(i+1)
-            } else {
-                None
-            },
-            _ => match_branches(s, i, g.get(&rule).unwrap(), g),
-        }
-    }
-}
-
-fn valid_string(s: &str, g: &Grammar) -> bool {
-    match match_rule(s, 0, Rule::Branch(0), g) {
-        Some(i) => i == s.len(),
-        None => false,
-    }
-}
+use aoc::runner::Runner;
+use day19::{parse_input, total_valid, valid_string, valid_string_table};
 
-fn build_grammar(lines: &Vec<String>) -> Grammar {
-    lines
-        .iter()
-        .fold(HashMap::new(), |mut acc, line| {
-            let parts: Vec<&str> = line.split(':').collect();
-            let branch = Rule::Branch(usize::from_str(parts[0]).unwrap());
-            let choices = parts[1]
-                .split('|')
-                .map(|p| p
-                     .trim()
-                     .split(' ')
-                     .map(|y| {
-                         let x = y.trim_matches('"');
-                         match usize::from_str(x) {
-                            Ok(n) => Rule::Branch(n),
-                            Err(_) => Rule::Leaf(x.chars().nth(0).unwrap()),
-                         }
-                     })
-                     .collect::<Vec<Rule>>()
-                )
-                .collect::<Vec<Vec<Rule>>>();
-
-            acc.insert(branch, choices);
-            acc
-        })
-}
-
-fn total_valid(lines: &Vec<String>, g: &Grammar) -> usize {
-    lines
-        .iter()
-        .filter(|line| valid_string(line, g))
-        .count()
-}
 
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(19);
+
     //========================================================
     //                     Part 1
     //
-    let lines = get_file_contents("data/input.txt")?;
-    let groups = &lines.into_iter().group_by(|line| !line.is_empty());
-    let grouped: Vec<Vec<String>> = groups
-        .into_iter()
-        .map(|(_, group)| group.collect::<Vec<String>>())
-        .filter(|g| !g[0].is_empty())
-        .collect();
-
-    let grammar = build_grammar(&grouped[0]);
+    let (grammar, messages) = parse_input(get_file_contents("data/input.txt")?);
 
-    println!("Day 19 / Part 1: {}", total_valid(&grouped[1], &grammar));
+    runner.variants(1)
+        .try_variant("table", || total_valid(&messages, &grammar, valid_string_table, Budget::new()))
+        .try_variant("backtracking", || total_valid(&messages, &grammar, valid_string, Budget::new()))
+        .run();
 
     //==========================================================
     //                     Part 2
     //
-    let lines = get_file_contents("data/input2.txt")?;
-    let groups = &lines.into_iter().group_by(|line| !line.is_empty());
-    let grouped: Vec<Vec<String>> = groups
-        .into_iter()
-        .map(|(_, group)| group.collect::<Vec<String>>())
-        .filter(|g| !g[0].is_empty())
-        .collect();
+    let (grammar, messages) = parse_input(get_file_contents("data/input2.txt")?);
 
-    let grammar = build_grammar(&grouped[0]);
-
-    println!("Day 19 / Part 2: {}", total_valid(&grouped[1], &grammar));
-    
+    runner.variants(2)
+        .try_variant("table", || total_valid(&messages, &grammar, valid_string_table, Budget::new()))
+        .try_variant("backtracking", || total_valid(&messages, &grammar, valid_string, Budget::new()))
+        .run();
+    runner.finish();
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_match_rule() {
-        let grammar = build_grammar(
-            &vec![
-                "0: 1 2".to_string(),
-                "1: \"a\"".to_string(),
-                "2: \"b\"".to_string(),
-            ],
-        );
-
-        assert_eq!(Some(1), match_rule("ab", 0, Rule::Leaf('a'), &grammar));
-        assert_eq!(None, match_rule("ab", 0, Rule::Leaf('b'), &grammar));
-        assert_eq!(Some(2), match_rule("ab", 1, Rule::Leaf('b'), &grammar));
-    }
-
-    #[test]
-    fn test_valid_string_basic() {
-        let grammar = build_grammar(
-            &vec![
-                "0: 1 2".to_string(),
-                "1: \"a\"".to_string(),
-                "2: 1 3 | 3 1".to_string(),
-                "3: \"b\"".to_string(),
-            ],
-        );
-
-        assert!(valid_string("aab", &grammar));
-        assert!(valid_string("aba", &grammar));
-        assert!(!valid_string("bbb", &grammar));
-    }
-
-    #[test]
-    fn test_valid_string_basic2() {
-        let grammar = build_grammar(
-            &vec![
-                "0: 4 1 5".to_string(),
-                "1: 2 3 | 3 2".to_string(),
-                "2: 4 4 | 5 5".to_string(),
-                "3: 4 5 | 5 4".to_string(),
-                "4: \"a\"".to_string(),
-                "5: \"b\"".to_string(),
-            ],
-        );
-
-        assert!(valid_string("ababbb", &grammar));
-        assert!(!valid_string("bababa", &grammar));
-        assert!(valid_string("abbbab", &grammar));
-        assert!(!valid_string("aaabbb", &grammar));
-        assert!(!valid_string("aaaabbb", &grammar));
-    }
-}
@@ -0,0 +1,246 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/2
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::policy::{Entry, Rule};
+
+
+/// How an entry of the database fared against a policy.
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct Record {
+    // From 1, as in the input
+    pub line: usize,
+    pub policy: String,
+    pub lo: usize,
+    pub hi: usize,
+    pub letter: char,
+    pub password: String,
+    pub count: usize,
+    pub positions: Vec<usize>,
+    pub valid: bool,
+    pub reason: String,
+}
+
+pub fn audit(entries: &[Entry], policy: &str, rule: &Rule) -> Vec<Record> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let verdict = rule.check(e);
+            let positions = e.positions();
+            let reasons: Vec<&str> = verdict.deciding().iter().map(|v| v.reason.as_str()).collect();
+
+            Record {
+                line: i + 1,
+                policy: policy.to_string(),
+                lo: e.lo,
+                hi: e.hi,
+                letter: e.letter,
+                password: e.password.clone(),
+                count: positions.len(),
+                positions,
+                valid: verdict.passed,
+                reason: reasons.join("; "),
+            }
+        })
+        .collect()
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = String::from("line,policy,lo,hi,letter,password,count,positions,valid,reason\n");
+
+    for r in records.iter() {
+        let positions: Vec<String> = r.positions.iter().map(|p| p.to_string()).collect();
+        let fields = [
+            r.line.to_string(),
+            csv_field(&r.policy),
+            r.lo.to_string(),
+            r.hi.to_string(),
+            csv_field(&r.letter.to_string()),
+            csv_field(&r.password),
+            r.count.to_string(),
+            positions.join(" "),
+            r.valid.to_string(),
+            csv_field(&r.reason),
+        ];
+
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).unwrap()
+}
+
+/// Writes `<path>.csv` and `<path>.json`.
+pub fn write(records: &[Record], path: &str) -> io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(format!("{}.csv", path), to_csv(records))?;
+    fs::write(format!("{}.json", path), to_json(records))
+}
+
+/// Aggregates of the records of a single policy.
+#[derive(Debug,PartialEq)]
+pub struct Summary {
+    pub policy: String,
+    pub valid: usize,
+    pub invalid: usize,
+    // Letters of the invalid entries, most frequent first
+    pub violated_letters: Vec<(char, usize)>,
+    // How many entries have their letter this many times
+    pub counts: BTreeMap<usize, usize>,
+}
+
+impl Summary {
+    pub fn new(policy: &str, records: &[Record]) -> Self {
+        let records: Vec<&Record> = records.iter().filter(|r| r.policy == policy).collect();
+        let mut violated: HashMap<char, usize> = HashMap::new();
+        let mut counts = BTreeMap::new();
+
+        for r in records.iter() {
+            if !r.valid {
+                *violated.entry(r.letter).or_insert(0) += 1;
+            }
+            *counts.entry(r.count).or_insert(0) += 1;
+        }
+
+        let mut violated_letters: Vec<(char, usize)> = violated.into_iter().collect();
+        violated_letters.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let valid = records.iter().filter(|r| r.valid).count();
+        Summary {
+            policy: policy.to_string(),
+            valid,
+            invalid: records.len() - valid,
+            violated_letters,
+            counts,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letters: Vec<String> = self.violated_letters
+            .iter()
+            .take(5)
+            .map(|(c, n)| format!("'{}' ({})", c, n))
+            .collect();
+        let counts: Vec<String> = self.counts
+            .iter()
+            .map(|(count, n)| format!("{}: {}", count, n))
+            .collect();
+
+        writeln!(f, "{}: {} valid, {} invalid", self.policy, self.valid, self.invalid)?;
+        writeln!(f, "    most violated letters: {}", letters.join(", "))?;
+        write!(f, "    entries by letter count: {}", counts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy;
+
+    fn entries() -> Vec<Entry> {
+        ["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc", "3-4 é: éé,\"é"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_audit() {
+        let records = audit(&entries(), "toboggan", &policy::builtin("toboggan").unwrap());
+
+        assert_eq!(
+            Record {
+                line: 1,
+                policy: "toboggan".to_string(),
+                lo: 1,
+                hi: 3,
+                letter: 'a',
+                password: "abcde".to_string(),
+                count: 1,
+                positions: vec![1],
+                valid: true,
+                reason: "'a' is at position 1 but not 3".to_string(),
+            },
+            records[0],
+        );
+        assert_eq!(vec![1, 2, 5], records[3].positions);
+        assert!(!records[3].valid);
+
+        let csv = to_csv(&records);
+        assert_eq!(5, csv.lines().count());
+        assert_eq!(
+            "4,toboggan,3,4,é,\"éé,\"\"é\",3,1 2 5,false,'é' is at neither position 3 nor 4",
+            csv.lines().last().unwrap(),
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&records)).unwrap();
+        assert_eq!(4, json.as_array().unwrap().len());
+        assert_eq!("é", json[3]["letter"]);
+        assert_eq!(serde_json::json!([1, 2, 5]), json[3]["positions"]);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut records = audit(&entries(), "sled", &policy::builtin("sled").unwrap());
+        records.extend(audit(&entries(), "toboggan", &policy::builtin("toboggan").unwrap()));
+
+        let summary = Summary::new("sled", &records);
+        assert_eq!((3, 1), (summary.valid, summary.invalid));
+        assert_eq!(
+            "sled: 3 valid, 1 invalid\n    \
+             most violated letters: 'b' (1)\n    \
+             entries by letter count: 0: 1, 1: 1, 3: 1, 9: 1",
+            summary.to_string(),
+        );
+
+        let summary = Summary::new("toboggan", &records);
+        assert_eq!((1, 3), (summary.valid, summary.invalid));
+        assert_eq!(vec![('b', 1), ('c', 1), ('é', 1)], summary.violated_letters);
+    }
+}
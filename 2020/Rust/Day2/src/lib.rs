@@ -0,0 +1,86 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/2
+
+use aoc::explain;
+
+pub mod audit;
+pub mod policy;
+
+use policy::{Entry, Rule};
+
+
+pub fn count_valid_passwords(entries: &[Entry], rule: &Rule) -> usize {
+    entries
+        .iter()
+        .filter(|e| {
+            let verdict = rule.check(e);
+            explain!("policy", "{}: {}", e, verdict);
+            verdict.passed
+        })
+        .count()
+}
+
+pub fn parse_entries(lines: &[String]) -> Vec<Entry> {
+    lines
+        .iter()
+        .map(|line| line.parse().unwrap_or_else(|e| panic!("{}", e)))
+        .collect()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let name = match part {
+        1 => "sled",
+        2 => "toboggan",
+        _ => return None,
+    };
+
+    Some(count_valid_passwords(&parse_entries(lines), &policy::builtin(name).unwrap()).to_string())
+}
+
+aoc::plugin!(day: 2, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: [&str; 3] = [
+        "1-3 a: abcde",
+        "1-3 b: cdefg",
+        "2-9 c: ccccccccc",
+    ];
+
+    fn entries() -> Vec<Entry> {
+        parse_entries(&RULES.iter().map(|&s| String::from(s)).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn test_count_valid_passwords_part1() {
+        assert_eq!(2, count_valid_passwords(&entries(), &policy::builtin("sled").unwrap()));
+    }
+
+    #[test]
+    fn test_count_valid_passwords_part2() {
+        assert_eq!(1, count_valid_passwords(&entries(), &policy::builtin("toboggan").unwrap()));
+    }
+}
@@ -22,105 +22,88 @@
 
 // https://adventofcode.com/2020/day/2
 
-#![allow(bare_trait_objects)]
 #![allow(non_snake_case)]
 
 use std::collections::HashMap;
-use std::str::FromStr;
 
+use aoc::config;
 use aoc::fs::get_file_contents;
-use lazy_static::lazy_static;
-use regex::Regex;
-
-
-type CheckerFn = Fn(usize,usize,char,String) -> bool;
-
-
-fn is_valid_password(password: &str, checker: &CheckerFn) -> bool {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) (\w{1}): (\w+)").unwrap();
-    }
-
-    match RE.captures(password) {
-        None => panic!("Malformed entry: {}", password),
-        Some(c) => {
-            let _min = usize::from_str(c.get(1).unwrap().as_str()).unwrap();
-            let _max = usize::from_str(c.get(2).unwrap().as_str()).unwrap();
-            let letter = char::from_str(c.get(3).unwrap().as_str()).unwrap();
-            let passwd = c.get(4).unwrap().as_str();
+use aoc::runner::Runner;
+use day2::policy::{self, Rule};
+use day2::{audit, count_valid_passwords, parse_entries};
+use serde::Deserialize;
+
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    // Policy each part checks the entries against
+    part1: String,
+    part2: String,
+    // Policies besides the built-in `sled` and `toboggan`, by name
+    policies: HashMap<String, Rule>,
+    // Where to write the audit of both parts, as `<audit>.csv` and
+    // `<audit>.json`
+    audit: Option<String>,
+}
 
-            checker(_min, _max, letter, passwd.to_string())
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            part1: "sled".to_string(),
+            part2: "toboggan".to_string(),
+            policies: HashMap::new(),
+            audit: None,
         }
     }
 }
 
-fn count_valid_passwords_part1(passwords: &Vec<String>) -> usize {
-    passwords
-        .iter()
-        .filter(|p| is_valid_password(p, &|_min, _max, letter, passwd: String| {
-            let count = passwd.chars().filter(|c| *c == letter).count();
-            count >= _min && count <= _max
-        }))
-        .count()
+impl Params {
+    fn policy(&self, name: &str) -> Option<Rule> {
+        self.policies.get(name).cloned().or_else(|| policy::builtin(name))
+    }
 }
 
-fn count_valid_passwords_part2(passwords: &Vec<String>) -> usize {
-    passwords
-        .iter()
-        .filter(|p| is_valid_password(p, &|first, second, letter, passwd: String| {
-            let indices = passwd
-                .as_str()
-                .char_indices()
-                .fold(HashMap::new(), |mut acc, opt| {
-                    let (i, c) = opt;
-                    acc.insert(i+1, c);
-                    acc
-                });
-
-            let a = *indices.get(&first).unwrap();
-            let b = *indices.get(&second).unwrap();
-
-            (a == letter || b == letter) && !(a == letter && b == letter)
-        }))
-        .count()
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        for name in [&self.part1, &self.part2].iter() {
+            if self.policy(name).is_none() {
+                return Err(format!("There's no policy named {}", name));
+            }
+        }
+
+        Ok(())
+    }
 }
 
+
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(2);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
+    let entries = runner.parsed("entries", || parse_entries(&lines));
+    let (part1, part2) = (params.policy(&params.part1).unwrap(), params.policy(&params.part2).unwrap());
+
+    runner.part(1, || count_valid_passwords(&entries, &part1));
+    runner.part(2, || count_valid_passwords(&entries, &part2));
+
+    if let Some(path) = &params.audit {
+        let mut policies = vec![&params.part1];
+        let mut records = audit::audit(&entries, &params.part1, &part1);
+        if params.part2 != params.part1 {
+            policies.push(&params.part2);
+            records.extend(audit::audit(&entries, &params.part2, &part2));
+        }
 
-    println!("Day 2 / Part 1: {}", count_valid_passwords_part1(&lines));
-    println!("Day 2 / Part 2: {}", count_valid_passwords_part2(&lines));
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const RULES: [&str; 3] = [
-        "1-3 a: abcde",
-        "1-3 b: cdefg",
-        "2-9 c: ccccccccc",
-    ];
-
-    #[test]
-    fn test_count_valid_passwords_part1() {
-        assert_eq!(
-            2,
-            count_valid_passwords_part1(
-                &RULES.to_vec().iter().map(|&s| String::from(s)).collect(),
-            )
-        );
+        audit::write(&records, path)?;
+        println!("Audit written to {}.csv and {}.json", path, path);
+        for policy in policies {
+            println!("{}", audit::Summary::new(policy, &records));
+        }
     }
 
-    #[test]
-    fn test_count_valid_passwords_part2() {
-        assert_eq!(
-            1,
-            count_valid_passwords_part2(
-                &RULES.to_vec().iter().map(|&s| String::from(s)).collect(),
-            )
-        );
-    }
+    runner.finish();
+
+    Ok(())
 }
@@ -0,0 +1,337 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/2
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::config::{self, TextPattern};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde::de::{Deserializer, Error, MapAccess, Visitor};
+
+
+/// A line of the password database, e.g. `1-3 a: abcde`.
+#[derive(Clone,Debug,Deserialize,PartialEq,Serialize)]
+pub struct Entry {
+    pub lo: usize,
+    pub hi: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+impl FromStr for Entry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) (\S): (\S+)$").unwrap();
+        }
+
+        let c = RE.captures(s).ok_or_else(|| format!("Malformed entry: {}", s))?;
+        let number = |i| usize::from_str(&c[i]).map_err(|_| format!("Malformed entry: {}", s));
+
+        Ok(Entry {
+            lo: number(1)?,
+            hi: number(2)?,
+            letter: c[3].chars().next().unwrap(),
+            password: c[4].to_string(),
+        })
+    }
+}
+
+impl Entry {
+    /// Where the letter is in the password, counting characters from 1.
+    pub fn positions(&self) -> Vec<usize> {
+        self.password
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c == self.letter)
+            .map(|(i, _)| i + 1)
+            .collect()
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}: {}", self.lo, self.hi, self.letter, self.password)
+    }
+}
+
+/// A password policy. In `aoc.toml` rules are written like `"count"`,
+/// `{ forbidden = "iol" }` or `{ any = ["positions_xor", { regex = "^a" }] }`.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Rule {
+    /// The letter occurs between `lo` and `hi` times.
+    Count,
+    /// Exactly one of the positions `lo` and `hi` (from 1) holds the letter.
+    PositionsXor,
+    /// Both positions hold the letter.
+    PositionsAnd,
+    /// None of these characters occurs.
+    Forbidden(String),
+    /// The password matches somewhere, unless the regex is anchored.
+    Regex(TextPattern),
+    All(Vec<Rule>),
+    Any(Vec<Rule>),
+}
+
+struct RuleVisitor;
+
+impl<'de> Visitor<'de> for RuleVisitor {
+    type Value = Rule;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a rule, like \"count\" or { forbidden = \"iol\" }")
+    }
+
+    fn visit_str<E: Error>(self, s: &str) -> Result<Rule, E> {
+        match s {
+            "count" => Ok(Rule::Count),
+            "positions_xor" => Ok(Rule::PositionsXor),
+            "positions_and" => Ok(Rule::PositionsAnd),
+            _ => Err(E::unknown_variant(s, &["count", "positions_xor", "positions_and"])),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Rule, A::Error> {
+        config::single_entry(map, "rule", |key, map| match key {
+            "forbidden" => Ok(Rule::Forbidden(map.next_value()?)),
+            "regex" => Ok(Rule::Regex(map.next_value()?)),
+            "all" => Ok(Rule::All(map.next_value()?)),
+            "any" => Ok(Rule::Any(map.next_value()?)),
+            _ => Err(A::Error::unknown_variant(key, &["forbidden", "regex", "all", "any"])),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RuleVisitor)
+    }
+}
+
+/// The policies each part of the puzzle checks, by name.
+pub fn builtin(name: &str) -> Option<Rule> {
+    match name {
+        "sled" => Some(Rule::Count),
+        "toboggan" => Some(Rule::PositionsXor),
+        _ => None,
+    }
+}
+
+/// Whether an entry follows a rule, and why.
+#[derive(Clone,Debug,PartialEq)]
+pub struct Verdict {
+    pub passed: bool,
+    pub reason: String,
+    // Verdicts of the rules combined by `all` or `any`
+    pub parts: Vec<Verdict>,
+}
+
+impl Verdict {
+    fn leaf(passed: bool, reason: String) -> Self {
+        Verdict { passed, reason, parts: Vec::new() }
+    }
+
+    /// The rules that decided the verdict: the failed ones when it failed,
+    /// and the passed ones otherwise.
+    pub fn deciding(&self) -> Vec<&Verdict> {
+        if self.parts.is_empty() {
+            return vec![self];
+        }
+
+        self.parts
+            .iter()
+            .filter(|p| p.passed == self.passed)
+            .flat_map(|p| p.deciding())
+            .collect()
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reasons: Vec<&str> = self.deciding().iter().map(|v| v.reason.as_str()).collect();
+        write!(f, "{} ({})", if self.passed { "valid" } else { "invalid" }, reasons.join("; "))
+    }
+}
+
+fn describe_positions(e: &Entry) -> (bool, bool, String) {
+    // Positions count characters, not bytes
+    let at = |pos: usize| pos >= 1 && e.password.chars().nth(pos - 1) == Some(e.letter);
+    let (a, b) = (at(e.lo), at(e.hi));
+
+    let reason = match (a, b) {
+        (true, true) => format!("'{}' is at both positions {} and {}", e.letter, e.lo, e.hi),
+        (true, false) => format!("'{}' is at position {} but not {}", e.letter, e.lo, e.hi),
+        (false, true) => format!("'{}' is at position {} but not {}", e.letter, e.hi, e.lo),
+        (false, false) => format!("'{}' is at neither position {} nor {}", e.letter, e.lo, e.hi),
+    };
+
+    (a, b, reason)
+}
+
+impl Rule {
+    pub fn check(&self, e: &Entry) -> Verdict {
+        match self {
+            Rule::Count => {
+                let count = e.positions().len();
+                let passed = count >= e.lo && count <= e.hi;
+
+                Verdict::leaf(passed, format!(
+                    "'{}' occurs {} {}, {} {}-{}",
+                    e.letter, count, if count == 1 { "time" } else { "times" },
+                    if passed { "within" } else { "outside" }, e.lo, e.hi,
+                ))
+            },
+            Rule::PositionsXor => {
+                let (a, b, reason) = describe_positions(e);
+                Verdict::leaf(a != b, reason)
+            },
+            Rule::PositionsAnd => {
+                let (a, b, reason) = describe_positions(e);
+                Verdict::leaf(a && b, reason)
+            },
+            Rule::Forbidden(chars) => match e.password.chars().find(|&c| chars.contains(c)) {
+                Some(c) => Verdict::leaf(false, format!("contains forbidden '{}'", c)),
+                None => Verdict::leaf(true, format!("contains none of {:?}", chars)),
+            },
+            Rule::Regex(pattern) => {
+                let passed = pattern.is_match(&e.password);
+                Verdict::leaf(passed, format!(
+                    "{} /{}/", if passed { "matches" } else { "doesn't match" }, pattern,
+                ))
+            },
+            Rule::All(rules) | Rule::Any(rules) => {
+                let parts: Vec<Verdict> = rules.iter().map(|r| r.check(e)).collect();
+                let passed = match self {
+                    Rule::All(_) => parts.iter().all(|p| p.passed),
+                    _ => parts.iter().any(|p| p.passed),
+                };
+                let failed = parts.iter().filter(|p| !p.passed).count();
+
+                Verdict {
+                    passed,
+                    reason: format!("{} of {} rules failed", failed, parts.len()),
+                    parts,
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    fn entry(s: &str) -> Entry {
+        s.parse().unwrap()
+    }
+
+    #[derive(Deserialize)]
+    struct Policy {
+        rule: Rule,
+    }
+
+    fn rule(toml: &str) -> Rule {
+        ::toml::from_str::<Policy>(&format!("rule = {}", toml)).unwrap().rule
+    }
+
+    #[test]
+    fn test_entry() {
+        assert_eq!(
+            Entry { lo: 1, hi: 3, letter: 'é', password: "aébé".to_string() },
+            entry("1-3 é: aébé"),
+        );
+        assert_eq!("1-3 é: aébé", entry("1-3 é: aébé").to_string());
+        assert!("1-3 a abcde".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn test_positions() {
+        assert_eq!(vec![1, 3], entry("1-3 a: abade").positions());
+        assert_eq!(vec![2, 4], entry("1-3 é: aébé").positions());
+        assert!(entry("1-3 z: abade").positions().is_empty());
+    }
+
+    #[test]
+    fn test_rules() {
+        let e = entry("1-3 a: abcde");
+
+        assert_eq!("valid ('a' occurs 1 time, within 1-3)", Rule::Count.check(&e).to_string());
+        assert_eq!(
+            "invalid ('c' occurs 0 times, outside 1-3)",
+            Rule::Count.check(&entry("1-3 c: abade")).to_string(),
+        );
+        assert_eq!(
+            "valid ('a' is at position 1 but not 3)",
+            Rule::PositionsXor.check(&e).to_string(),
+        );
+        assert!(!Rule::PositionsAnd.check(&e).passed);
+
+        // Positions are characters, not bytes
+        let e = entry("2-4 é: aébé");
+        assert!(!Rule::PositionsXor.check(&e).passed);
+        assert!(Rule::PositionsAnd.check(&e).passed);
+        assert!(!Rule::PositionsAnd.check(&entry("3-9 é: aébé")).passed);
+    }
+
+    #[test]
+    fn test_combined_rules() {
+        let strict = rule(r#"{ all = ["count", { forbidden = "iol" }, { regex = "^[a-z]+$" }] }"#);
+        assert_eq!(
+            Rule::All(vec![
+                Rule::Count,
+                Rule::Forbidden("iol".to_string()),
+                Rule::Regex(TextPattern::new("^[a-z]+$").unwrap()),
+            ]),
+            strict,
+        );
+
+        assert!(strict.check(&entry("1-3 a: abcde")).passed);
+        assert_eq!(
+            "invalid (contains forbidden 'o'; doesn't match /^[a-z]+$/)",
+            strict.check(&entry("1-3 a: abcdeoA")).to_string(),
+        );
+
+        let lenient = rule(r#"{ any = ["positions_and", "count"] }"#);
+        assert_eq!(
+            "valid ('a' occurs 1 time, within 1-3)",
+            lenient.check(&entry("1-3 a: abcde")).to_string(),
+        );
+        assert!(!lenient.check(&entry("2-3 b: cdefg")).passed);
+
+        assert!(::toml::from_str::<Policy>(r#"rule = { regex = "(" }"#).is_err());
+        assert!(::toml::from_str::<Policy>(r#"rule = "length""#).is_err());
+        assert!(::toml::from_str::<Policy>(r#"rule = { all = [], any = [] }"#).is_err());
+
+        // Parameters are read through a `toml::Value`
+        let value: ::toml::Value = ::toml::from_str(r#"rule = { any = ["count", { regex = "a" }] }"#).unwrap();
+        assert_eq!(
+            Rule::Any(vec![Rule::Count, Rule::Regex(TextPattern::new("a").unwrap())]),
+            value.try_into::<Policy>().unwrap().rule,
+        );
+    }
+}
@@ -1,2 +1 @@
 pub mod tile;
-pub mod image;
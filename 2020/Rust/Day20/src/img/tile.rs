@@ -31,14 +31,6 @@ use std::str::FromStr;
 type Edge = String;
 type Matrix = Vec<String>;
 
-#[derive(Clone,Copy)]
-pub enum TileEdge {
-    Top,
-    Left,
-    Right,
-    Bottom,
-}
-
 #[derive(Clone,Debug,Eq,Hash,PartialEq)]
 pub struct Tile {
     pub id: i32,
@@ -47,7 +39,7 @@ pub struct Tile {
 
 impl fmt::Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let t = vec![
+        let t = [
             format!("Id: {}", self.id),
             self.matrix.join("\n"),
         ];
@@ -57,17 +49,13 @@ impl fmt::Display for Tile {
 }
 
 impl Tile {
-    pub fn new(lines: &Vec<String>) -> Self {
+    pub fn new(lines: &[String]) -> Self {
         Tile {
             id: i32::from_str(&lines[0].split(':').collect::<Vec<&str>>()[0][5..]).unwrap(),
             matrix: lines[1..].to_vec(),
         }
     }
 
-    pub fn size(&self) -> usize {
-        self.matrix.len()
-    }
-
     pub fn left_edge(&self) -> Edge {
         (0..self.matrix.len())
             .map(|row| self.matrix[row].chars().nth(0).unwrap())
@@ -90,15 +78,6 @@ impl Tile {
         self.matrix.iter().last().unwrap().to_string()
     }
 
-    pub fn edge(&self, e: TileEdge) -> Edge {
-        match e {
-            TileEdge::Top => self.top_edge(),
-            TileEdge::Left => self.left_edge(),
-            TileEdge::Bottom => self.bottom_edge(),
-            TileEdge::Right => self.right_edge(),
-        }
-    }
-
     pub fn edges(&self) -> Vec<Edge> {
         vec![
             self.top_edge(),
@@ -118,81 +97,4 @@ impl Tile {
 
         edges
     }
-
-    fn strip_border(&self) -> Tile {
-        let height = self.matrix.len();
-        let width = height;
-        let new_matrix = self.matrix[1..=height-1].to_vec();
-
-        Tile {
-            id: self.id,
-            matrix: new_matrix
-                .iter()
-                .map(|row| row[1..=width-1].to_string())
-                .collect()
-        }
-    }
-
-    fn flip(&self) -> Tile {
-        Tile {
-            id: self.id,
-            matrix: self.matrix
-                .iter()
-                .map(|row| row.chars().rev().collect())
-                .clone()
-                .collect()
-        }
-    }
-
-    pub fn rotate(&self) -> Tile {
-        let m = (0..self.matrix.len())
-            .map(|row| {
-                self.matrix
-                    .iter()
-                    .map(|r| r.chars().nth(row).unwrap())
-                    .clone()
-                    .rev()
-                    .collect::<String>()
-            })
-            .collect();
-
-        Tile {
-            id: self.id,
-            matrix: m,
-        }
-    }
-
-    pub fn transformations(&self) -> Transformation {
-        Transformation::new(self.clone())
-    }
-}
-
-pub struct Transformation {
-    curr: Tile,
-    counter: usize,
-}
-
-impl Transformation {
-    fn new(t: Tile) -> Self {
-        Transformation {
-            curr: t,
-            counter: 0,
-        }
-    }
-}
-
-impl Iterator for Transformation {
-    type Item = Tile;
-
-    fn next(&mut self) -> Option<Tile> {
-        self.counter += 1;
-
-        match self.counter % 3 {
-            0 => self.curr = self.curr.rotate(),
-            2 => self.curr = self.curr.flip(),
-            _ => (),
-        }
-        
-        Some(self.curr.clone())
-    }
 }
@@ -0,0 +1,95 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/20
+
+use std::collections::HashMap;
+
+use aoc::explain;
+use itertools::Itertools;
+
+pub mod img;
+
+use img::tile::Tile;
+
+
+/// The tiles, by id.
+pub fn parse_tiles(lines: Vec<String>) -> HashMap<i32, Tile> {
+    let raw_tiles = lines.into_iter().group_by(|line| !line.is_empty());
+    let grouped: Vec<Vec<String>> = raw_tiles
+        .into_iter()
+        .map(|(_, group)| group.collect::<Vec<String>>())
+        .filter(|g| !g[0].is_empty())
+        .collect();
+
+    grouped
+        .iter()
+        .fold(HashMap::new(), |mut acc, t| {
+            let tile = Tile::new(t);
+            acc.insert(tile.id, tile);
+            acc
+        })
+}
+
+/// Maps the edges to all the Tiles that use them.
+pub fn shared_edges(tiles: &HashMap<i32, Tile>) -> HashMap<String, Vec<&Tile>> {
+    let mut edges: HashMap<String, Vec<&Tile>> = HashMap::new();
+
+    for tile in tiles.values() {
+        for e in tile.all_edges() {
+            edges.entry(e).or_default().push(tile);
+        }
+    }
+
+    edges
+}
+
+pub fn corners_product(tiles: &HashMap<i32, Tile>, edges: &HashMap<String, Vec<&Tile>>) -> i64 {
+    // Finds the corners, which are the Tiles that only have two
+    // edges shared with other Tiles.
+    let mut corners: Vec<&Tile> = Vec::with_capacity(4);
+
+    for t in tiles.values() {
+        let c = t.edges().iter().fold(0, |acc, e| {
+            acc + (edges.get(e).unwrap().len() - 1)
+        });
+
+        if c == 2 {
+            explain!("corner", "tile {} shares only two edges", t.id);
+            corners.push(t);
+        }
+    }
+
+    // Calculates the product of the four corners
+    corners.iter().map(|t| t.id).fold(1_i64, |acc, id| acc * (id as i64))
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let tiles = parse_tiles(lines.to_vec());
+
+    match part {
+        1 => Some(corners_product(&tiles, &shared_edges(&tiles)).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 20, parts: 1, solve: solve);
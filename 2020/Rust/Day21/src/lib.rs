@@ -0,0 +1,191 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/21
+
+use std::collections::{HashMap,HashSet};
+use std::iter::FromIterator;
+
+use aoc::budget::{Budget, DidNotConverge};
+use aoc::explain;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+
+pub fn build_allergens(foods: &[Vec<Vec<String>>]) -> HashMap<String, HashSet<String>> {
+    let mut res: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for food in foods.iter() {
+        for allergen in food[0].iter() {
+            if !res.contains_key(allergen) {
+                res.insert(
+                    allergen.to_string(),
+                    HashSet::from_iter(food[1].iter().cloned()),
+                );
+            } else {
+                let ii = res.get(allergen).unwrap();
+                res.insert(
+                    allergen.to_string(),
+                    ii.intersection(
+                        &HashSet::from_iter(food[1].iter().cloned())
+                    ).cloned().collect());
+            }
+        }
+    }
+
+    res
+}
+
+pub fn raw_data(lines: &[String]) -> Vec<Vec<Vec<String>>> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"([\w\s]+) \(contains (.*)\)").unwrap();
+    }
+
+    let mut res = Vec::new();
+
+    for line in lines.iter() {
+        match RE.captures(line) {
+            None => panic!("Malformed entry: {}", line),
+            Some(c) => {
+                let ingredients: Vec<String> =
+                    c.get(1).unwrap().as_str()
+                        .split(' ')
+                        .map(|w| w.trim().to_string())
+                        .clone()
+                        .collect();
+                let allergens: Vec<String> =
+                    c.get(2).unwrap().as_str()
+                        .split(',')
+                        .map(|w| w.trim().to_string())
+                        .clone()
+                        .collect();
+
+                res.push(vec![allergens, ingredients]);
+            },
+        }
+    }
+
+    res
+}
+
+/*
+ * As a result of building the allergens map, there is at least one allergen
+ * whose HashSet of potential matched ingredients only has one element.
+ * Such allergen will be our starting point.
+ */
+pub fn resolve_allergens(
+    ai: &mut HashMap<String, HashSet<String>>,
+    budget: &mut Budget,
+) -> Result<(), DidNotConverge> {
+    let allergens: Vec<String> = ai.keys().cloned().collect();
+    let mut resolved: HashSet<String> = HashSet::new();
+
+    loop {
+        if let Err(e) = budget.tick() {
+            return Err(e.with_state(allergens_state(ai)));
+        }
+
+        let mut progress = false;
+
+        for a in allergens.iter() {
+            let ii = ai.get(a).unwrap().len();
+            if ii == 1 {
+                let to_remove = ai.get(a).unwrap().iter().last().cloned().unwrap();
+                if resolved.insert(a.to_string()) {
+                    explain!("allergen", "{} can only be in {}", a, to_remove);
+                }
+
+                for (allergen, ingredients) in ai.iter_mut() {
+                    if allergen != a {
+                        progress |= ingredients.remove(&to_remove);
+                    }
+                }
+            }
+        }
+
+        if ai.values().all(|ii| ii.len() == 1) { break ; }
+
+        if !progress {
+            return Err(DidNotConverge::stalled(allergens_state(ai)));
+        }
+    }
+
+    Ok(())
+}
+
+// Each allergen along with the ingredients that might still contain it.
+fn allergens_state(ai: &HashMap<String, HashSet<String>>) -> String {
+    let mut lines: Vec<String> = ai
+        .iter()
+        .map(|(a, ii)| {
+            let mut ii: Vec<&String> = ii.iter().collect();
+            ii.sort();
+            format!("{}: {}", a, ii.iter().map(|i| i.as_str()).collect::<Vec<&str>>().join(", "))
+        })
+        .collect();
+
+    lines.sort();
+    lines.join("\n")
+}
+
+/// How many times the ingredients that can't contain an allergen appear.
+pub fn safe_ingredients(foods: &[Vec<Vec<String>>], ai: &HashMap<String, HashSet<String>>) -> usize {
+    // Builds a HashMap that maps each ingredient that doesn't contain an
+    // allergen to the number of times it occurs.
+    let mut occur: HashMap<&String, usize> = HashMap::new();
+
+    for food in foods.iter() {
+        for ingredient in food[1].iter() {
+            if !ai.values().any(|i| i.iter().last().unwrap() == ingredient) {
+                *occur.entry(ingredient).or_insert(0) += 1;
+            }
+        }
+    }
+
+    occur.values().sum::<usize>()
+}
+
+/// The dangerous ingredients, sorted by their allergen.
+pub fn canonical_dangerous(ai: &HashMap<String, HashSet<String>>) -> String {
+    let mut canonical = ai.iter().fold(Vec::new(), |mut acc, (k, v)| {
+        acc.push((k, v.iter().last().unwrap()));
+        acc
+    });
+    canonical.sort_unstable();
+    let canonical_form: Vec<String> = canonical.iter().map(|(_, v)| v.to_string()).collect();
+
+    canonical_form.join(",")
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let foods = raw_data(lines);
+    let mut ai = build_allergens(&foods);
+    resolve_allergens(&mut ai, &mut Budget::new()).unwrap_or_else(|e| panic!("{}", e));
+
+    match part {
+        1 => Some(safe_ingredients(&foods, &ai).to_string()),
+        2 => Some(canonical_dangerous(&ai)),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 21, parts: 2, solve: solve);
@@ -0,0 +1,254 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/22
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::iter::FromIterator;
+
+use aoc::budget::{Budget, DidNotConverge};
+use aoc::sim::Simulation;
+
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub enum Player {
+    You,
+    Crab,
+}
+
+pub type Hand = VecDeque<usize>;
+
+pub trait GamePlay {
+    fn play_game(
+        you: &[usize],
+        crab: &[usize],
+        budget: &mut Budget,
+    ) -> Result<(Player, Hand), DidNotConverge>;
+
+    fn round_winner(you: &Hand, crab: &Hand) -> Player {
+        match you[0].cmp(&crab[0]) {
+            Ordering::Less => Player::Crab,
+            Ordering::Greater => Player::You,
+            Ordering::Equal => panic!("Invalid hands: {:?} / {:?}", you, crab),
+        }
+    }
+
+    fn game_winner(you: &Hand, crab: &Hand) -> Option<Player> {
+        if you.is_empty() {
+            Some(Player::Crab)
+        } else if crab.is_empty() {
+            Some(Player::You)
+        } else {
+            None
+        }
+    }
+
+    fn score(hand: &Hand) -> usize {
+        hand
+            .iter()
+            .zip((1..=hand.len()).rev())
+            .fold(0_usize, |acc, (card, i)| acc + card*i)
+    }
+}
+
+pub struct RegularCombat;
+
+// Nothing stops a game of Regular Combat from going on forever, so it's
+// played within a budget.
+impl GamePlay for RegularCombat {
+    fn play_game(
+        hand1: &[usize],
+        hand2: &[usize],
+        budget: &mut Budget,
+    ) -> Result<(Player, Hand), DidNotConverge> {
+        let mut you = VecDeque::from_iter(hand1.to_vec().iter().cloned());
+        let mut crab = VecDeque::from_iter(hand2.to_vec().iter().cloned());
+
+        loop {
+            if let Some(p) = Self::game_winner(&you, &crab) {
+                return Ok((p, if p == Player::You { you } else { crab }))
+            }
+
+            if let Err(e) = budget.tick() {
+                return Err(e.with_state(format!(
+                    "after {} rounds\nyou: {:?}\ncrab: {:?}",
+                    budget.steps(), you, crab,
+                )));
+            }
+
+            match Self::round_winner(&you, &crab) {
+                Player::You => {
+                    you.rotate_left(1);
+                    you.push_back(crab.pop_front().unwrap());
+                },
+                Player::Crab => {
+                    crab.rotate_left(1);
+                    crab.push_back(you.pop_front().unwrap());
+                },
+            }
+        }
+    }
+}
+
+pub struct RecursiveCombat;
+
+// The state of a game of Recursive Combat at the start of a round.
+#[derive(Clone,Debug,Eq,Hash,PartialEq)]
+struct RecursiveGame {
+    you: Hand,
+    crab: Hand,
+}
+
+impl Simulation for RecursiveGame {
+    // Plays a single round. Once someone runs out of cards, the game
+    // doesn't change anymore.
+    fn step(&mut self) {
+        let (you, crab) = (&mut self.you, &mut self.crab);
+
+        if RecursiveCombat::game_winner(you, crab).is_some() {
+            return;
+        }
+
+        // Do both players have a hand that has at least as many cards as
+        // the value of their top card? If so, we go recursive to determine
+        // the winner of the round. If not, the winner of the round is
+        // determined using the normal rules.
+        let winner = if RecursiveCombat::go_recursive(you, crab) {
+            let ny = VecDeque::from_iter(you.iter().skip(1).take(you[0]).cloned());
+            let nc = VecDeque::from_iter(crab.iter().skip(1).take(crab[0]).cloned());
+            let (w, _) = RecursiveCombat::play(ny, nc);
+            w
+        } else {
+            RecursiveCombat::round_winner(you, crab)
+        };
+
+        match winner {
+            Player::You => {
+                you.rotate_left(1);
+                you.push_back(crab.pop_front().unwrap());
+            },
+            Player::Crab => {
+                crab.rotate_left(1);
+                crab.push_back(you.pop_front().unwrap());
+            },
+        }
+    }
+}
+
+impl RecursiveCombat {
+    fn go_recursive(you: &Hand, crab: &Hand) -> bool {
+        you.len() > you[0] && crab.len() > crab[0]
+    }
+
+    fn play(you: Hand, crab: Hand) -> (Player, Hand) {
+        let mut game = RecursiveGame { you, crab };
+
+        // The game ends either when a player runs out of cards, which is a
+        // fixed point, or as soon as the hands repeat themselves, in which
+        // case You win. Either way, we stop at the first repeated state.
+        game.run_until_repeat();
+
+        match Self::game_winner(&game.you, &game.crab) {
+            Some(Player::Crab) => (Player::Crab, game.crab),
+            _ => (Player::You, game.you),
+        }
+    }
+}
+
+// Recursive Combat always ends, since repeated hands end the game.
+impl GamePlay for RecursiveCombat {
+    fn play_game(
+        hand1: &[usize],
+        hand2: &[usize],
+        _budget: &mut Budget,
+    ) -> Result<(Player, Hand), DidNotConverge> {
+        let you = VecDeque::from_iter(hand1.to_vec().iter().cloned());
+        let crab = VecDeque::from_iter(hand2.to_vec().iter().cloned());
+
+        Ok(Self::play(you, crab))
+    }
+}
+
+pub fn crab_combat<T>(hand1: &[usize], hand2: &[usize], budget: &mut Budget) -> Result<usize, DidNotConverge>
+where
+    T: GamePlay
+{
+    let (_, winner_hand) = T::play_game(hand1, hand2, budget)?;
+    Ok(T::score(&winner_hand))
+}
+
+pub const PLAYER1: [usize; 25] = [
+    21, 22, 33, 29, 43,
+    35, 8,  30, 50, 44,
+    9,  42, 45, 16, 12,
+    4,  15, 27, 20, 31,
+    25, 47, 5,  24, 19,
+];
+
+pub const PLAYER2: [usize; 25] = [
+    3,  40, 37, 14, 1,
+    13, 49, 41, 28, 48,
+    18, 7,  23, 38, 32,
+    34, 46, 39, 17, 2,
+    11, 6,  10, 36, 26,
+];
+
+// The decks are in the code, so there's no input to read
+fn solve(part: u8, _lines: &[String]) -> Option<String> {
+    let score = match part {
+        1 => crab_combat::<RegularCombat>(&PLAYER1, &PLAYER2, &mut Budget::new()),
+        2 => crab_combat::<RecursiveCombat>(&PLAYER1, &PLAYER2, &mut Budget::new()),
+        _ => return None,
+    };
+
+    Some(score.unwrap_or_else(|e| panic!("{}", e)).to_string())
+}
+
+aoc::plugin!(day: 22, parts: 2, solve: solve, reads_input: false);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regular_combat() {
+        assert_eq!(
+            Ok(306),
+            crab_combat::<RegularCombat>(&[9,2,6,3,1], &[5,8,4,7,10], &mut Budget::unlimited()),
+        );
+
+        // This one loops forever under the regular rules
+        let e = crab_combat::<RegularCombat>(&[43,19], &[2,29,14], &mut Budget::with_max_steps(1000))
+            .unwrap_err();
+        assert_eq!("did not converge (ran out of its 1000 steps)", e.to_string());
+    }
+
+    #[test]
+    fn test_recursive_combat() {
+        assert_eq!(
+            Ok(291),
+            crab_combat::<RecursiveCombat>(&[9,2,6,3,1], &[5,8,4,7,10], &mut Budget::unlimited()),
+        );
+        assert!(crab_combat::<RecursiveCombat>(&[43,19], &[2,29,14], &mut Budget::unlimited()).is_ok());
+    }
+}
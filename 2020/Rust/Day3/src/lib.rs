@@ -0,0 +1,99 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/3
+
+pub mod toboggan;
+
+use toboggan::{Map, Slope};
+
+
+// (right, down)
+pub const SLOPES: [(i64, i64); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+pub fn count_trees(map: &Map, (dx, dy): (i64, i64), vertical_wrap: bool) -> usize {
+    toboggan::ride(map, Slope::new(dx, dy), vertical_wrap).trees
+}
+
+pub fn count_trees_all_slopes(map: &Map, slopes: &[(i64, i64)], vertical_wrap: bool) -> usize {
+    slopes
+        .iter()
+        .fold(1, |acc, &slope| {
+            acc * count_trees(map, slope, vertical_wrap)
+        })
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let map = Map::new(lines).unwrap_or_else(|e| panic!("{}", e));
+
+    match part {
+        1 => Some(count_trees(&map, (3, 1), false).to_string()),
+        2 => Some(count_trees_all_slopes(&map, &SLOPES, false).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 3, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: [&str; 11] = [
+        "..##.......",
+        "#...#...#..",
+        ".#....#..#.",
+        "..#.#...#.#",
+        ".#...##..#.",
+        "..#.##.....",
+        ".#.#.#....#",
+        ".#........#",
+        "#.##...#...",
+        "#...##....#",
+        ".#..#...#.#",
+    ];
+
+    fn map() -> Map {
+        Map::new(&MAP.to_vec().iter().map(|&l| String::from(l)).collect::<Vec<String>>()).unwrap()
+    }
+
+    #[test]
+    fn test_count_trees() {
+        assert_eq!(7, count_trees(&map(), (3, 1), false));
+        assert_eq!(3, count_trees(&map(), (-3, 1), false));
+    }
+
+    #[test]
+    fn test_count_trees_all_slopes() {
+        assert_eq!(336, count_trees_all_slopes(&map(), &SLOPES, false));
+    }
+
+    #[test]
+    fn test_vertical_wrap() {
+        // 11 columns and 11 rows, so every slope is back at the corner
+        // after 11 squares
+        let ride = toboggan::ride(&map(), Slope::new(3, 1), true);
+        assert_eq!(11, ride.path.len());
+        assert_eq!(7, ride.trees);
+        assert_eq!(11, toboggan::ride(&map(), Slope::new(1, 2), true).path.len());
+    }
+}
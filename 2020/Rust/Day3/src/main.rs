@@ -24,79 +24,97 @@
 
 #![allow(non_snake_case)]
 
+use aoc::config;
 use aoc::fs::get_file_contents;
+use aoc::runner::Runner;
+use day3::toboggan::{self, Map};
+use day3::{count_trees, count_trees_all_slopes, SLOPES};
+use serde::Deserialize;
+
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    slope: (i64, i64),
+    slopes: Vec<(i64, i64)>,
+    // Whether the map also repeats itself downwards
+    vertical_wrap: bool,
+    // Ranges of the slopes to search for the fewest and most trees
+    explore: Option<Explore>,
+    render: bool,
+}
 
-fn count_trees(lines: &Vec<String>, dx: usize, dy: usize) -> usize {
-    let width = lines[0].len();
-    let mut x = 0;
-    let mut num_trees = 0_usize;
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Explore {
+    dx: (i64, i64),
+    dy: (i64, i64),
+}
 
-    for i in (0..lines.len()).step_by(dy) {
-        if lines[i].chars().nth(x).unwrap() == '#' {
-            num_trees += 1;
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            slope: (3, 1),
+            slopes: SLOPES.to_vec(),
+            vertical_wrap: false,
+            explore: None,
+            render: false,
         }
-
-        x = (x + dx) % width;
     }
+}
+
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.slopes.iter().chain(Some(&self.slope)).any(|&(_, dy)| dy < 1) {
+            return Err("slopes must go down at least one row".to_string());
+        }
+
+        if let Some(Explore { dx, dy }) = &self.explore {
+            if dx.0 > dx.1 || dy.0 > dy.1 || dy.1 < 1 {
+                return Err(format!(
+                    "explore must have dx = [min, max] and dy = [min, max] with max >= 1, not dx = [{}, {}], dy = [{}, {}]",
+                    dx.0, dx.1, dy.0, dy.1,
+                ));
+            }
+        }
 
-    num_trees
+        Ok(())
+    }
 }
 
-fn count_trees_all_slopes(lines: &Vec<String>) -> usize {
-    [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
-        .iter()
-        .fold(1, |acc, (x, y)| {
-            acc * count_trees(lines, *x, *y)
-        })
+
+fn print_extremes(map: &Map, explore: &Explore, vertical_wrap: bool, render: bool) {
+    let (fewest, most) = toboggan::extremes(
+        map,
+        explore.dx.0..=explore.dx.1,
+        explore.dy.0.max(1)..=explore.dy.1,
+        vertical_wrap,
+    ).unwrap();
+
+    for (name, ride) in [("Fewest", fewest), ("Most", most)].iter() {
+        println!("{} trees: {} ({})", name, ride.trees, ride.slope);
+        if render {
+            println!("{}", toboggan::render(map, ride));
+        }
+    }
 }
 
 
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(3);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
+    let map = Map::new(&lines).unwrap_or_else(|e| panic!("{}", e));
 
-    println!("Day 3 / Part 1: {}", count_trees(&lines, 3, 1));
-    println!("Day 3 / Part 2: {}", count_trees_all_slopes(&lines));
+    runner.part(1, || count_trees(&map, params.slope, params.vertical_wrap));
+    runner.part(2, || count_trees_all_slopes(&map, &params.slopes, params.vertical_wrap));
 
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const MAP: [&str; 11] = [
-        "..##.......",
-        "#...#...#..",
-        ".#....#..#.",
-        "..#.#...#.#",
-        ".#...##..#.",
-        "..#.##.....",
-        ".#.#.#....#",
-        ".#........#",
-        "#.##...#...",
-        "#...##....#",
-        ".#..#...#.#",
-    ];
-
-    #[test]
-    fn test_count_trees() {
-        assert_eq!(
-            7,
-            count_trees(
-                &MAP.to_vec().iter().map(|&l| String::from(l)).collect(),
-                3,
-                1,
-            ),
-        );
+    if let Some(explore) = &params.explore {
+        print_extremes(&map, explore, params.vertical_wrap, params.render);
     }
 
-    #[test]
-    fn test_count_trees_all_slopes() {
-        assert_eq!(
-            336,
-            count_trees_all_slopes(
-                &MAP.to_vec().iter().map(|&l| String::from(l)).collect(),
-            ),
-        );
-    }
+    runner.finish();
+
+    Ok(())
 }
@@ -0,0 +1,231 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/3
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+
+/// The area to cross, which repeats itself to the right (and left).
+#[derive(Clone,Debug,PartialEq)]
+pub struct Map {
+    width: usize,
+    // Whether there's a tree at each square, a row per line
+    trees: Vec<Vec<bool>>,
+}
+
+impl Map {
+    /// Fails if there are no rows, or they aren't all as wide as the first.
+    pub fn new(lines: &[String]) -> Result<Self, String> {
+        let trees: Vec<Vec<bool>> = lines
+            .iter()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+
+        let width = match trees.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err("The map is empty".to_string()),
+        };
+
+        if let Some(y) = trees.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {} is {} squares wide, but the first is {}", y, trees[y].len(), width,
+            ));
+        }
+
+        Ok(Map { width, trees })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn is_tree(&self, x: usize, y: usize) -> bool {
+        self.trees[y][x % self.width]
+    }
+}
+
+/// Steps of `dx` squares to the right (or left, if negative) and `dy` down.
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Slope {
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Slope {
+    pub fn new(dx: i64, dy: i64) -> Self {
+        Slope { dx, dy }
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "right {}, down {}", self.dx, self.dy)
+    }
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub struct Ride {
+    pub slope: Slope,
+    // Squares visited, from the top left corner, as (column, row)
+    pub path: Vec<(usize, usize)>,
+    pub trees: usize,
+}
+
+/// Rides down from the top left corner until past the bottom. With
+/// `vertical_wrap`, the map also repeats itself downwards, and the ride goes
+/// on until it's back at the corner (which it always is, since every square
+/// is reached from a single other one).
+pub fn ride(map: &Map, slope: Slope, vertical_wrap: bool) -> Ride {
+    assert!(slope.dy >= 1, "Toboggans only go down, not {}", slope);
+
+    let (w, h) = (map.width() as i64, map.height() as i64);
+    let (mut x, mut y) = (0_i64, 0_i64);
+    let mut path = Vec::new();
+
+    loop {
+        path.push((x as usize, y as usize));
+
+        x = (x + slope.dx).rem_euclid(w);
+        y += slope.dy;
+        if vertical_wrap {
+            y %= h;
+        }
+
+        if y >= h || (x, y) == (0, 0) {
+            break;
+        }
+    }
+
+    let trees = path.iter().filter(|&&(x, y)| map.is_tree(x, y)).count();
+    Ride { slope, path, trees }
+}
+
+/// The rides with the fewest and the most trees among every slope in the
+/// ranges. Ties go to the first slope, by `dy` and then `dx`.
+pub fn extremes(
+    map: &Map,
+    dx: RangeInclusive<i64>,
+    dy: RangeInclusive<i64>,
+    vertical_wrap: bool,
+) -> Option<(Ride, Ride)> {
+    let mut res: Option<(Ride, Ride)> = None;
+
+    for dy in dy.filter(|&dy| dy >= 1) {
+        for dx in dx.clone() {
+            let r = ride(map, Slope::new(dx, dy), vertical_wrap);
+
+            res = match res {
+                None => Some((r.clone(), r)),
+                Some((fewest, most)) => Some((
+                    if r.trees < fewest.trees { r.clone() } else { fewest },
+                    if r.trees > most.trees { r } else { most },
+                )),
+            };
+        }
+    }
+
+    res
+}
+
+/// The map with the squares visited by the ride marked, `O` when open and
+/// `X` on a tree. The ride wraps around the edges like the map does.
+pub fn render(map: &Map, ride: &Ride) -> String {
+    let mut visited = vec![vec![false; map.width()]; map.height()];
+
+    for &(x, y) in ride.path.iter() {
+        visited[y][x] = true;
+    }
+
+    (0..map.height())
+        .map(|y| {
+            (0..map.width())
+                .map(|x| match (visited[y][x], map.is_tree(x, y)) {
+                    (true, true) => 'X',
+                    (true, false) => 'O',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> Result<Map, String> {
+        Map::new(&lines.iter().map(|&l| String::from(l)).collect::<Vec<String>>())
+    }
+
+    fn map(lines: &[&str]) -> Map {
+        parse(lines).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(Err("The map is empty".to_string()), parse(&[]));
+        assert_eq!(Err("The map is empty".to_string()), parse(&["", ""]));
+        assert_eq!(
+            Err("Row 2 is 2 squares wide, but the first is 3".to_string()),
+            parse(&["..#", "#..", ".#"]),
+        );
+    }
+
+    #[test]
+    fn test_ride() {
+        let m = map(&["..#", "#..", ".#."]);
+
+        let r = ride(&m, Slope::new(-1, 1), false);
+        assert_eq!(vec![(0, 0), (2, 1), (1, 2)], r.path);
+        assert_eq!(1, r.trees);
+        assert_eq!(0, ride(&m, Slope::new(1, 1), false).trees);
+        assert_eq!(vec![(0, 0), (0, 2)], ride(&m, Slope::new(0, 2), false).path);
+
+        let r = ride(&m, Slope::new(1, 2), true);
+        assert_eq!(vec![(0, 0), (1, 2), (2, 1)], r.path);
+        assert_eq!(1, r.trees);
+
+        // Back at the corner after going down the whole map once
+        let r = ride(&m, Slope::new(0, 1), true);
+        assert_eq!(vec![(0, 0), (0, 1), (0, 2)], r.path);
+    }
+
+    #[test]
+    fn test_extremes_and_render() {
+        let m = map(&["..#", "#..", ".#."]);
+        let (fewest, most) = extremes(&m, -1..=1, 0..=1, false).unwrap();
+
+        assert_eq!((Slope::new(1, 1), 0), (fewest.slope, fewest.trees));
+        assert_eq!((Slope::new(-1, 1), 1), (most.slope, most.trees));
+        assert_eq!("O.#\n#.O\n.X.", render(&m, &most));
+        assert_eq!("O.#\n#O.\n.#O", render(&m, &fewest));
+        assert!(extremes(&m, -1..=1, -2..=0, false).is_none());
+    }
+}
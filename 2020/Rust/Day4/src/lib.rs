@@ -0,0 +1,147 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/4
+
+pub mod passport;
+pub mod report;
+pub mod schema;
+
+use report::Mode;
+use schema::Schema;
+
+
+pub fn count_valid_passports(schema: &Schema, content: &[String], mode: Mode) -> usize {
+    passport::parse(content)
+        .iter()
+        .filter(|p| report::check(schema, p, mode).valid)
+        .count()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let mode = match part {
+        1 => Mode::Presence,
+        2 => Mode::Full,
+        _ => return None,
+    };
+
+    Some(count_valid_passports(&Schema::passport(), lines, mode).to_string())
+}
+
+aoc::plugin!(day: 4, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema::{Check, Unknown};
+
+    fn is_valid_field(field: &str, value: &str) -> bool {
+        Schema::passport().check(field, value) == Check::Valid
+    }
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|&line| String::from(line)).collect()
+    }
+
+    #[test]
+    fn test_no_valid_passports() {
+        assert_eq!(
+            0,
+            count_valid_passports(&Schema::passport(), &lines(&["eyr:2027", "", "byr:1981"]), Mode::Full),
+        );
+    }
+
+    #[test]
+    fn test_two_valid_passport() {
+        assert_eq!(
+            2,
+            count_valid_passports(
+                &Schema::passport(),
+                &lines(&[
+                    "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd",
+                    "byr:1937 iyr:2017 cid:147 hgt:183cm",
+                    "",
+                    "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884",
+                    "hcl:#cfa07d byr:1929",
+                    "",
+                    "hcl:#ae17e1 iyr:2013",
+                    "eyr:2024",
+                    "ecl:brn pid:760753108 byr:1931",
+                    "hgt:179cm",
+                    "",
+                    "hcl:#cfa07d eyr:2025 pid:166559648",
+                    "iyr:2011 ecl:brn hgt:59in"
+                ]),
+                Mode::Full,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_presence_only() {
+        let passports = lines(&[
+            "eyr:1972 cid:100",
+            "hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+            "",
+            "iyr:2019",
+            "hcl:#602927 eyr:1967 hgt:170cm",
+            "ecl:grn pid:012533040 byr:1946",
+            "",
+            "hcl:dab227 iyr:2012",
+            "ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277",
+            "",
+            "hgt:59cm ecl:zzz",
+            "eyr:2038 hcl:74454a iyr:2023",
+            "pid:3556412378 byr:2007",
+        ]);
+
+        assert_eq!(4, count_valid_passports(&Schema::passport(), &passports, Mode::Presence));
+        assert_eq!(0, count_valid_passports(&Schema::passport(), &passports, Mode::Full));
+    }
+
+    #[test]
+    fn test_valid_fields() {
+        assert!(is_valid_field("byr", "2002"));
+        assert!(!is_valid_field("byr", "2003"));
+        assert!(is_valid_field("hgt", "60in"));
+        assert!(is_valid_field("hgt", "190cm"));
+        assert!(!is_valid_field("hgt", "190in"));
+        assert!(!is_valid_field("hgt", "190"));
+        assert!(is_valid_field("hcl", "#123abc"));
+        assert!(!is_valid_field("hcl", "#123abz"));
+        assert!(!is_valid_field("hcl", "123abc"));
+        assert!(is_valid_field("ecl", "brn"));
+        assert!(!is_valid_field("ecl", "wat"));
+        assert!(is_valid_field("pid", "000000001"));
+        assert!(!is_valid_field("pid", "0123456789"));
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let passport = lines(&["byr:1937 iyr:2017 eyr:2020 hgt:183cm", "hcl:#fffffd ecl:gry pid:860033327 foo:bar"]);
+        let mut schema = Schema::passport();
+
+        assert_eq!(0, count_valid_passports(&schema, &passport, Mode::Full));
+        schema.unknown = Unknown::Ignore;
+        assert_eq!(1, count_valid_passports(&schema, &passport, Mode::Full));
+    }
+}
@@ -0,0 +1,124 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/4
+
+
+/// A record of the batch file: `key:value` pairs over one or more lines,
+/// up to a blank line.
+#[derive(Clone,Debug,PartialEq)]
+pub struct Passport {
+    // Lines of the input it spans, from 1
+    pub first_line: usize,
+    pub last_line: usize,
+    // In the order they were written, repeated keys included
+    pub fields: Vec<(String, String)>,
+}
+
+pub fn parse(lines: &[String]) -> Vec<Passport> {
+    let mut passports = Vec::new();
+    let mut current: Option<Passport> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            passports.extend(current.take());
+            continue;
+        }
+
+        let passport = current.get_or_insert_with(|| Passport {
+            first_line: i + 1,
+            last_line: i + 1,
+            fields: Vec::new(),
+        });
+
+        passport.last_line = i + 1;
+        passport.fields.extend(pairs(line));
+    }
+
+    passports.extend(current);
+    passports
+}
+
+// The `key:value` pairs of a line, even with spaces around the colons.
+fn pairs(line: &str) -> Vec<(String, String)> {
+    let mut parts: Vec<String> = Vec::new();
+
+    for part in line.split_whitespace() {
+        match parts.last_mut() {
+            Some(last) if (last.ends_with(':') && !part.contains(':'))
+                || (part.starts_with(':') && !last.contains(':')) => last.push_str(part),
+            _ => parts.push(part.to_string()),
+        }
+    }
+
+    parts
+        .iter()
+        .map(|part| {
+            let mut it = part.splitn(2, ':');
+            (it.next().unwrap().to_string(), it.next().unwrap_or("").to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(p: &Passport, key: &str) -> Option<String> {
+        p.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn test_parse() {
+        let lines: Vec<String> = ["a:1 b:2", "c:3", "", "", "a:4 a:5", "d"]
+            .iter()
+            .map(|&l| String::from(l))
+            .collect();
+        let passports = parse(&lines);
+
+        assert_eq!(2, passports.len());
+        assert_eq!((1, 2), (passports[0].first_line, passports[0].last_line));
+        assert_eq!(Some("3".to_string()), field(&passports[0], "c"));
+        assert_eq!((5, 6), (passports[1].first_line, passports[1].last_line));
+        assert_eq!(3, passports[1].fields.len());
+        assert_eq!(Some("4".to_string()), field(&passports[1], "a"));
+        assert_eq!(Some(String::new()), field(&passports[1], "d"));
+        assert_eq!(None, field(&passports[1], "b"));
+    }
+
+    #[test]
+    fn test_stray_whitespace() {
+        let lines: Vec<String> = ["  ", " a:1\t b : 2\r", "c: 3 d :4 e:", "f:6", " \t", "", "g:7"]
+            .iter()
+            .map(|&l| String::from(l))
+            .collect();
+        let passports = parse(&lines);
+
+        assert_eq!(2, passports.len());
+        assert_eq!((2, 4), (passports[0].first_line, passports[0].last_line));
+        assert_eq!(
+            ["a:1", "b:2", "c:3", "d:4", "e:", "f:6"].to_vec(),
+            passports[0].fields.iter().map(|(k, v)| format!("{}:{}", k, v)).collect::<Vec<String>>(),
+        );
+        assert_eq!(7, passports[1].first_line);
+    }
+}
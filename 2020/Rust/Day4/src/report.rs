@@ -0,0 +1,220 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/4
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::passport::Passport;
+use crate::schema::{Check, Schema, Unknown};
+
+
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct Invalid {
+    pub field: String,
+    pub value: String,
+    pub rule: String,
+}
+
+/// How thoroughly passports are checked.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Mode {
+    /// Only whether the required fields are there, whatever their values.
+    Presence,
+    /// Also whether their values follow the rules.
+    Full,
+}
+
+/// Why a passport is valid or not.
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct Report {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub valid: bool,
+    // Required fields that aren't there
+    pub missing: Vec<String>,
+    pub invalid: Vec<Invalid>,
+    // Fields that are there more than once
+    pub duplicates: Vec<String>,
+    // Fields that aren't in the schema
+    pub unknown: Vec<String>,
+}
+
+/// With `Mode::Full` every value is checked, so a field that is there twice
+/// is invalid if either of its values is. In both modes duplicates make the
+/// passport invalid, and so do unknown fields when the schema rejects them.
+pub fn check(schema: &Schema, passport: &Passport, mode: Mode) -> Report {
+    let mut seen = HashSet::new();
+    let mut invalid = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut unknown = Vec::new();
+
+    for (field, value) in passport.fields.iter() {
+        if !seen.insert(field.as_str()) && !duplicates.contains(field) {
+            duplicates.push(field.clone());
+        }
+
+        match schema.check(field, value) {
+            Check::Valid => (),
+            Check::Invalid(_) if mode == Mode::Presence => (),
+            Check::Invalid(rule) => invalid.push(Invalid {
+                field: field.clone(),
+                value: value.clone(),
+                rule: rule.to_string(),
+            }),
+            Check::Unknown(_) => unknown.push(field.clone()),
+        }
+    }
+
+    let missing: Vec<String> = schema.fields
+        .iter()
+        .filter(|f| f.required && !seen.contains(f.name.as_str()))
+        .map(|f| f.name.clone())
+        .collect();
+
+    Report {
+        first_line: passport.first_line,
+        last_line: passport.last_line,
+        valid: missing.is_empty()
+            && invalid.is_empty()
+            && duplicates.is_empty()
+            && (unknown.is_empty() || schema.unknown != Unknown::Reject),
+        missing,
+        invalid,
+        duplicates,
+        unknown,
+    }
+}
+
+/// A row per passport, with the reasons it's invalid.
+pub fn table(reports: &[Report]) -> String {
+    let mut rows = vec![
+        ["lines", "verdict", "missing", "invalid", "duplicates", "unknown"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+    ];
+
+    for r in reports.iter() {
+        let invalid: Vec<String> = r.invalid
+            .iter()
+            .map(|i| format!("{}:{} ({})", i.field, i.value, i.rule))
+            .collect();
+
+        rows.push(vec![
+            if r.first_line == r.last_line {
+                r.first_line.to_string()
+            } else {
+                format!("{}-{}", r.first_line, r.last_line)
+            },
+            (if r.valid { "valid" } else { "invalid" }).to_string(),
+            r.missing.join(","),
+            invalid.join(", "),
+            r.duplicates.join(","),
+            r.unknown.join(","),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|c| rows.iter().map(|row| row[c].chars().count()).max().unwrap())
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(cell, &w)| format!("{:w$}", cell, w = w))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn to_json(reports: &[Report]) -> String {
+    serde_json::to_string_pretty(reports).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passport;
+
+    fn reports_in(mode: Mode, lines: &[&str]) -> Vec<Report> {
+        let lines: Vec<String> = lines.iter().map(|&l| String::from(l)).collect();
+        passport::parse(&lines).iter().map(|p| check(&Schema::passport(), p, mode)).collect()
+    }
+
+    fn reports(lines: &[&str]) -> Vec<Report> {
+        reports_in(Mode::Full, lines)
+    }
+
+    #[test]
+    fn test_check() {
+        let r = reports(&[
+            "byr:1937 iyr:2017 eyr:2020 hgt:183cm",
+            "hcl:#fffffd ecl:gry pid:860033327",
+            "",
+            "byr:2003 iyr:2017 eyr:2020 hgt:183 hgt:183cm",
+            "ecl:gry pid:860033327 foo:bar",
+        ]);
+
+        assert!(r[0].valid);
+        assert_eq!((4, 5), (r[1].first_line, r[1].last_line));
+        assert!(!r[1].valid);
+        assert_eq!(vec!["hcl"], r[1].missing);
+        assert_eq!(
+            Invalid { field: "byr".to_string(), value: "2003".to_string(), rule: "range 1920-2002".to_string() },
+            r[1].invalid[0],
+        );
+        assert_eq!("hgt", r[1].invalid[1].field);
+        assert_eq!(vec!["hgt"], r[1].duplicates);
+        assert_eq!(vec!["foo"], r[1].unknown);
+    }
+
+    #[test]
+    fn test_presence() {
+        let lines = ["byr:2003 iyr:2017 eyr:2020 hgt:183 hcl:#fffffd ecl:gry pid:860033327"];
+
+        assert!(!reports(&lines)[0].valid);
+        assert!(reports_in(Mode::Presence, &lines)[0].valid);
+        assert!(reports_in(Mode::Presence, &lines)[0].invalid.is_empty());
+        assert!(!reports_in(Mode::Presence, &["byr:2003 cid:1"])[0].valid);
+    }
+
+    #[test]
+    fn test_table() {
+        let r = reports(&["byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327", "", "cid:1 cid:2"]);
+
+        assert_eq!(
+            "lines  verdict  missing                      invalid  duplicates  unknown\n\
+             1      valid\n\
+             3      invalid  byr,iyr,eyr,hgt,hcl,ecl,pid           cid",
+            table(&r),
+        );
+        assert!(to_json(&r).contains("\"duplicates\": [\n      \"cid\"\n    ]"));
+    }
+}
@@ -0,0 +1,321 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/4
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc::config::{self, TextPattern};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use serde::de::{Deserializer, Error, MapAccess, Visitor};
+
+
+/// The rules from the puzzle.
+pub const PASSPORT: &str = r##"
+unknown = "reject"
+
+[[fields]]
+name = "byr"
+rule = { range = [1920, 2002] }
+
+[[fields]]
+name = "iyr"
+rule = { range = [2010, 2020] }
+
+[[fields]]
+name = "eyr"
+rule = { range = [2020, 2030] }
+
+[[fields]]
+name = "hgt"
+rule = { units = { cm = [150, 193], in = [59, 76] } }
+
+[[fields]]
+name = "hcl"
+rule = { regex = "^#[0-9a-f]{6}$" }
+
+[[fields]]
+name = "ecl"
+rule = { one_of = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"] }
+
+[[fields]]
+name = "pid"
+rule = { regex = "^[0-9]{9}$" }
+
+[[fields]]
+name = "cid"
+required = false
+"##;
+
+/// What a value must look like. In the schema validators are written like
+/// `{ range = [1920, 2002] }` or `{ one_of = ["amb", "blu"] }`.
+#[derive(Clone,Debug,PartialEq)]
+pub enum Validator {
+    /// An integer between the bounds, inclusive.
+    Range(i64, i64),
+    /// An integer followed by one of the units, and between its bounds,
+    /// e.g. `183cm`.
+    Units(BTreeMap<String, (i64, i64)>),
+    /// The whole value matches, whether or not the regex is anchored.
+    Regex(TextPattern),
+    OneOf(Vec<String>),
+}
+
+struct ValidatorVisitor;
+
+impl<'de> Visitor<'de> for ValidatorVisitor {
+    type Value = Validator;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a validator, like { range = [1920, 2002] }")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Validator, A::Error> {
+        config::single_entry(map, "validator", |key, map| match key {
+            "range" => {
+                let (lo, hi) = map.next_value()?;
+                Ok(Validator::Range(lo, hi))
+            },
+            "units" => Ok(Validator::Units(map.next_value()?)),
+            "regex" => {
+                let source: String = map.next_value()?;
+                TextPattern::whole(&source).map(Validator::Regex).map_err(A::Error::custom)
+            },
+            "one_of" => Ok(Validator::OneOf(map.next_value()?)),
+            _ => Err(A::Error::unknown_variant(key, &["range", "units", "regex", "one_of"])),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Validator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValidatorVisitor)
+    }
+}
+
+impl Validator {
+    pub fn accepts(&self, value: &str) -> bool {
+        lazy_static! {
+            static ref MEASURE: Regex = Regex::new(r"^(\d+)([a-z]+)$").unwrap();
+        }
+
+        match self {
+            Validator::Range(lo, hi) => i64::from_str(value).is_ok_and(|v| (*lo..=*hi).contains(&v)),
+            Validator::Units(units) => match MEASURE.captures(value) {
+                None => false,
+                Some(c) => match (i64::from_str(&c[1]), units.get(&c[2])) {
+                    (Ok(v), Some(&(lo, hi))) => (lo..=hi).contains(&v),
+                    _ => false,
+                },
+            },
+            Validator::Regex(pattern) => pattern.is_match(value),
+            Validator::OneOf(values) => values.iter().any(|v| v == value),
+        }
+    }
+}
+
+impl fmt::Display for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Validator::Range(lo, hi) => write!(f, "range {}-{}", lo, hi),
+            Validator::Units(units) => {
+                let units: Vec<String> = units
+                    .iter()
+                    .map(|(unit, (lo, hi))| format!("{}-{}{}", lo, hi, unit))
+                    .collect();
+                write!(f, "units {}", units.join(" or "))
+            },
+            Validator::Regex(pattern) => write!(f, "regex {}", pattern),
+            Validator::OneOf(values) => write!(f, "one of {}", values.join(", ")),
+        }
+    }
+}
+
+/// What to do with fields that aren't in the schema.
+#[derive(Clone,Copy,Debug,Default,Deserialize,PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Unknown {
+    #[default]
+    Ignore,
+    /// Ignore them, but say so.
+    Warn,
+    /// The document is invalid.
+    Reject,
+}
+
+fn required() -> bool {
+    true
+}
+
+#[derive(Clone,Debug,Deserialize,PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Field {
+    pub name: String,
+    #[serde(default = "required")]
+    pub required: bool,
+    // Any value goes when there's none
+    pub rule: Option<Validator>,
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum Check<'a> {
+    Valid,
+    Invalid(&'a Validator),
+    Unknown(Unknown),
+}
+
+#[derive(Clone,Debug,Deserialize,PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    #[serde(default)]
+    pub unknown: Unknown,
+    pub fields: Vec<Field>,
+}
+
+impl FromStr for Schema {
+    type Err = String;
+
+    /// Reads a schema written in TOML.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str::<Schema>(s).map_err(|e| e.to_string())?.validated()
+    }
+}
+
+impl Schema {
+    pub fn passport() -> Self {
+        Schema::from_str(PASSPORT).unwrap()
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        serde_json::from_str::<Schema>(s).map_err(|e| e.to_string())?.validated()
+    }
+
+    /// Reads a schema from a `.json` file, or a TOML one otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+
+        match path.extension() {
+            Some(ext) if ext == "json" => Schema::from_json(&contents),
+            _ => Schema::from_str(&contents),
+        }
+        .map_err(|e| format!("Invalid schema {}: {}", path.display(), e))
+    }
+
+    fn validated(self) -> Result<Self, String> {
+        let mut names = HashSet::new();
+
+        for field in self.fields.iter() {
+            if !names.insert(&field.name) {
+                return Err(format!("{} is there twice", field.name));
+            }
+
+            let bounds: Vec<(i64, i64)> = match &field.rule {
+                Some(Validator::Range(lo, hi)) => vec![(*lo, *hi)],
+                Some(Validator::Units(units)) => units.values().cloned().collect(),
+                _ => vec![],
+            };
+            if let Some((lo, hi)) = bounds.into_iter().find(|(lo, hi)| lo > hi) {
+                return Err(format!("{} has an empty range {}-{}", field.name, lo, hi));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Where the field is in the schema.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f.name == name)
+    }
+
+    pub fn check(&self, name: &str, value: &str) -> Check<'_> {
+        match self.index(name).map(|i| &self.fields[i].rule) {
+            None => Check::Unknown(self.unknown),
+            Some(Some(rule)) if !rule.accepts(value) => Check::Invalid(rule),
+            Some(_) => Check::Valid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators() {
+        let schema = Schema::passport();
+        let valid = |name, value| schema.check(name, value) == Check::Valid;
+
+        assert!(valid("byr", "2002"));
+        assert!(!valid("byr", "2003"));
+        assert!(valid("hgt", "60in"));
+        assert!(valid("hgt", "190cm"));
+        assert!(!valid("hgt", "190in"));
+        assert!(!valid("hgt", "190"));
+        assert!(!valid("hgt", "190mm"));
+        assert!(valid("ecl", "brn"));
+        assert!(!valid("ecl", "wat"));
+        assert!(valid("cid", "anything"));
+        assert_eq!(Check::Unknown(Unknown::Reject), schema.check("xyz", "1"));
+        assert_eq!(
+            "units 150-193cm or 59-76in",
+            schema.fields[3].rule.as_ref().unwrap().to_string(),
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let json = Schema::from_json(r#"{
+            "unknown": "warn",
+            "fields": [
+                {"name": "id", "rule": {"regex": "^[0-9]+$"}},
+                {"name": "age", "required": false, "rule": {"range": [0, 150]}}
+            ]
+        }"#).unwrap();
+
+        assert_eq!(Unknown::Warn, json.unknown);
+        assert_eq!(Some(1), json.index("age"));
+        assert!(json.fields[0].required && !json.fields[1].required);
+        assert_eq!(Check::Unknown(Unknown::Warn), json.check("name", "x"));
+
+        assert!(Schema::from_str("[[fields]]\nname = \"a\"\n[[fields]]\nname = \"a\"").is_err());
+        assert!(Schema::from_str("[[fields]]\nname = \"a\"\nrule = { range = [2, 1] }").is_err());
+        assert!(Schema::from_str("[[fields]]\nname = \"a\"\nrule = { between = [1, 2] }").is_err());
+        assert!(Schema::from_str("unknown = \"drop\"\nfields = []").is_err());
+    }
+
+    #[test]
+    fn test_regex_matches_whole_value() {
+        let schema = Schema::from_str("[[fields]]\nname = \"pid\"\nrule = { regex = \"[0-9]{9}\" }").unwrap();
+
+        assert_eq!(Check::Valid, schema.check("pid", "000000001"));
+        assert_ne!(Check::Valid, schema.check("pid", "0123456789"));
+        assert_ne!(Check::Valid, schema.check("pid", "x000000001"));
+        assert_eq!("regex [0-9]{9}", schema.fields[0].rule.as_ref().unwrap().to_string());
+    }
+}
@@ -0,0 +1,233 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/5
+
+use std::fmt;
+
+use aoc::bits::BitSet;
+
+
+/// Letters a pass can have at most, which keeps the aircraft to a million
+/// seats or so.
+pub const MAX_LETTERS: u32 = 20;
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Seat {
+    pub row: usize,
+    pub col: usize,
+    pub id: usize,
+}
+
+/// Boarding passes of an aircraft with `2^row_bits` rows of `2^col_bits`
+/// seats. Each letter of a pass halves the rows (and then the columns) left,
+/// keeping the lower half for the first of its two letters and the upper
+/// half for the second.
+#[derive(Clone,Debug,PartialEq)]
+pub struct SeatCodec {
+    row_bits: u32,
+    col_bits: u32,
+    row_letters: [char; 2],
+    col_letters: [char; 2],
+}
+
+impl Default for SeatCodec {
+    fn default() -> Self {
+        SeatCodec::new(7, 3, ['F', 'B'], ['L', 'R']).unwrap()
+    }
+}
+
+impl SeatCodec {
+    pub fn new(
+        row_bits: u32,
+        col_bits: u32,
+        row_letters: [char; 2],
+        col_letters: [char; 2],
+    ) -> Result<Self, String> {
+        for (what, bits) in [("row", row_bits), ("column", col_bits)].iter() {
+            if *bits > MAX_LETTERS {
+                return Err(format!("A pass can't have more than {} {} letters, not {}", MAX_LETTERS, what, bits));
+            }
+        }
+
+        match row_bits.checked_add(col_bits) {
+            Some(len) if (1..=MAX_LETTERS).contains(&len) => (),
+            _ => {
+                return Err(format!(
+                    "A pass must have 1 to {} letters, not {} + {}",
+                    MAX_LETTERS, row_bits, col_bits,
+                ))
+            }
+        }
+
+        for letters in [row_letters, col_letters].iter() {
+            if letters[0] == letters[1] {
+                return Err(format!("Both halves are {}", letters[0]));
+            }
+        }
+
+        Ok(SeatCodec { row_bits, col_bits, row_letters, col_letters })
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn cols(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    pub fn seats(&self) -> usize {
+        self.rows() * self.cols()
+    }
+
+    pub fn seat(&self, id: usize) -> Seat {
+        Seat { row: id / self.cols(), col: id % self.cols(), id }
+    }
+
+    pub fn decode(&self, pass: &str) -> Result<Seat, String> {
+        let len = (self.row_bits + self.col_bits) as usize;
+        if pass.chars().count() != len {
+            return Err(format!("{} should have {} letters", pass, len));
+        }
+
+        let mut id = 0;
+        for (i, c) in pass.chars().enumerate() {
+            let letters = if i < self.row_bits as usize { self.row_letters } else { self.col_letters };
+            let bit = letters.iter().position(|&l| l == c).ok_or_else(|| {
+                format!("Unexpected {:?} at position {} of {}, expected {} or {}", c, i + 1, pass, letters[0], letters[1])
+            })?;
+
+            id = id << 1 | bit;
+        }
+
+        Ok(self.seat(id))
+    }
+
+    pub fn encode(&self, id: usize) -> Result<String, String> {
+        if id >= self.seats() {
+            return Err(format!("There's no seat {}, the last one is {}", id, self.seats() - 1));
+        }
+
+        let len = self.row_bits + self.col_bits;
+        Ok((0..len)
+            .map(|i| {
+                let letters = if i < self.row_bits { self.row_letters } else { self.col_letters };
+                letters[id >> (len - 1 - i) & 1]
+            })
+            .collect())
+    }
+
+    pub fn decode_all(&self, passes: &[String]) -> Result<Vec<Seat>, String> {
+        passes.iter().map(|pass| self.decode(pass)).collect()
+    }
+}
+
+/// The seats taken by the passes, to tell free seats apart.
+pub struct SeatMap<'a> {
+    codec: &'a SeatCodec,
+    taken: BitSet,
+}
+
+impl<'a> SeatMap<'a> {
+    pub fn new(codec: &'a SeatCodec, seats: &[Seat]) -> Self {
+        SeatMap { codec, taken: seats.iter().map(|s| s.id).collect() }
+    }
+
+    pub fn is_taken(&self, id: usize) -> bool {
+        self.taken.contains(id)
+    }
+
+    /// Free seats whose ids are just after and before taken ones, as the
+    /// puzzle puts it. These can be at either end of a row.
+    pub fn free_seats(&self) -> Vec<usize> {
+        (1..self.codec.seats().saturating_sub(1))
+            .filter(|&id| !self.is_taken(id) && self.is_taken(id - 1) && self.is_taken(id + 1))
+            .collect()
+    }
+}
+
+impl fmt::Display for SeatMap<'_> {
+    /// A line per row, with `#` for taken seats, `O` for the free seats
+    /// between taken ones and `.` for the rest.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let free: BitSet = self.free_seats().into_iter().collect();
+        let width = (self.codec.rows() - 1).to_string().len();
+
+        for row in 0..self.codec.rows() {
+            let seats: String = (0..self.codec.cols())
+                .map(|col| row * self.codec.cols() + col)
+                .map(|id| if self.is_taken(id) { '#' } else if free.contains(id) { 'O' } else { '.' })
+                .collect();
+
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:>w$} {}", row, seats, w = width)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec() {
+        let codec = SeatCodec::default();
+
+        assert_eq!(Seat { row: 70, col: 7, id: 567 }, codec.decode("BFFFBBFRRR").unwrap());
+        assert_eq!("BBFFBBFRLL", codec.encode(820).unwrap());
+        assert!((0..codec.seats()).all(|id| codec.decode(&codec.encode(id).unwrap()).unwrap().id == id));
+        assert!(codec.encode(1024).is_err());
+        assert_eq!(Err("BFFFBBFRR should have 10 letters".to_string()), codec.decode("BFFFBBFRR"));
+        assert_eq!(
+            Err("Unexpected 'R' at position 3 of BFRFBBFRRR, expected F or B".to_string()),
+            codec.decode("BFRFBBFRRR"),
+        );
+        assert!(SeatCodec::new(0, 0, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatCodec::new(u32::MAX, 1, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatCodec::new(1, u32::MAX, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatCodec::new(16, 16, ['F', 'B'], ['L', 'R']).is_err());
+        assert!(SeatCodec::new(MAX_LETTERS - 3, 3, ['F', 'B'], ['L', 'R']).is_ok());
+        assert!(SeatCodec::new(2, 2, ['F', 'F'], ['L', 'R']).is_err());
+    }
+
+    #[test]
+    fn test_other_layout() {
+        let codec = SeatCodec::new(2, 1, ['0', '1'], ['0', '1']).unwrap();
+
+        assert_eq!((4, 2), (codec.rows(), codec.cols()));
+        assert_eq!(Seat { row: 2, col: 1, id: 5 }, codec.decode("101").unwrap());
+
+        let seats: Vec<Seat> = ["000", "001", "011", "101", "111"]
+            .iter()
+            .map(|pass| codec.decode(pass).unwrap())
+            .collect();
+        let map = SeatMap::new(&codec, &seats);
+
+        assert_eq!(vec![2, 4, 6], map.free_seats());
+        assert_eq!("0 ##\n1 O#\n2 O#\n3 O#", map.to_string());
+    }
+}
@@ -0,0 +1,88 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/5
+
+pub mod codec;
+
+pub use codec::{Seat, SeatCodec, SeatMap};
+
+
+pub fn get_max_id(seats: &[Seat]) -> usize {
+    seats.iter().map(|seat| seat.id).max().unwrap_or(0)
+}
+
+pub fn find_missing_seat(codec: &SeatCodec, seats: &[Seat]) -> Option<usize> {
+    SeatMap::new(codec, seats).free_seats().first().cloned()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let codec = SeatCodec::default();
+    let seats = codec.decode_all(lines).unwrap_or_else(|e| panic!("{}", e));
+
+    match part {
+        1 => Some(get_max_id(&seats).to_string()),
+        2 => find_missing_seat(&codec, &seats).map(|id| id.to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 5, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seat(pass: &str) -> Seat {
+        SeatCodec::default().decode(pass).unwrap()
+    }
+
+    #[test]
+    fn test_seat_row() {
+        assert_eq!(70, seat("BFFFBBFRRR").row);
+        assert_eq!(14, seat("FFFBBBFRRR").row);
+        assert_eq!(102, seat("BBFFBBFRLL").row);
+    }
+
+    #[test]
+    fn test_seat_col() {
+        assert_eq!(7, seat("BFFFBBFRRR").col);
+        assert_eq!(7, seat("FFFBBBFRRR").col);
+        assert_eq!(4, seat("BBFFBBFRLL").col);
+    }
+
+    #[test]
+    fn test_seat_id() {
+        assert_eq!(567, seat("BFFFBBFRRR").id);
+        assert_eq!(119, seat("FFFBBBFRRR").id);
+        assert_eq!(820, seat("BBFFBBFRLL").id);
+    }
+
+    #[test]
+    fn test_missing_seat() {
+        let seats: Vec<Seat> = ["FFFFFFFLLL", "FFFFFFFLRL", "FFFFFFFRLL", "FFFFFFFRRL"].iter().map(|p| seat(p)).collect();
+
+        assert_eq!(6, get_max_id(&seats));
+        assert_eq!(Some(1), find_missing_seat(&SeatCodec::default(), &seats));
+        assert_eq!(None, find_missing_seat(&SeatCodec::default(), &seats[..1]));
+    }
+}
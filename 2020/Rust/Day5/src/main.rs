@@ -24,91 +24,74 @@
 
 #![allow(non_snake_case)]
 
+use aoc::config;
 use aoc::fs::get_file_contents;
+use aoc::runner::Runner;
+use day5::{find_missing_seat, get_max_id, SeatCodec, SeatMap};
+use serde::Deserialize;
+
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    row_bits: u32,
+    col_bits: u32,
+    // Letters that keep the lower and the upper half
+    row_letters: String,
+    col_letters: String,
+    seat_map: bool,
+}
 
-fn binary_search(
-    seat: &str,
-    lo: usize,
-    hi: usize,
-    lo_char: char,
-    hi_char: char
-) -> usize {
-    let (mut lo, mut hi) = (lo, hi);
-
-    for c in seat.chars() {
-        let mid = lo + (hi - lo) / 2;
-        if c == hi_char {
-            hi = mid;
-        } else if c == lo_char {
-            lo = mid;
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            row_bits: 7,
+            col_bits: 3,
+            row_letters: "FB".to_string(),
+            col_letters: "LR".to_string(),
+            seat_map: false,
         }
     }
-
-    hi
-}
-
-fn seat_row(seat: &str) -> usize {
-    binary_search(seat, 0, 127, 'B', 'F')
 }
 
-fn seat_col(seat: &str) -> usize {
-    binary_search(seat, 0, 7, 'R', 'L')
-}
-
-fn seat_id(seat: &str) -> usize {
-    seat_row(seat) * 8 + seat_col(seat)
+fn letters(s: &str) -> Result<[char; 2], String> {
+    match s.chars().collect::<Vec<char>>()[..] {
+        [lower, upper] => Ok([lower, upper]),
+        _ => Err(format!("expected two letters, not {:?}", s)),
+    }
 }
 
-fn get_max_id(seats: &Vec<String>) -> usize {
-    seats.iter().fold(0, |acc, seat| {
-        let id = seat_id(seat);
-        acc.max(id)
-    })
+impl Params {
+    fn codec(&self) -> Result<SeatCodec, String> {
+        SeatCodec::new(self.row_bits, self.col_bits, letters(&self.row_letters)?, letters(&self.col_letters)?)
+    }
 }
 
-fn find_missing_seat(seats: &Vec<String>) -> usize {
-    let min_id = seats.iter().map(|seat| seat_id(seat)).min().unwrap();
-    let max_id = get_max_id(seats);
-
-    let mut all_seats = vec![false; 1024];
-    for seat in seats.iter() {
-        all_seats[seat_id(seat)] = true;
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        self.codec().map(|_| ())
     }
-
-    (min_id..max_id).find(|id| !all_seats[*id]).unwrap()
 }
 
+
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(5);
+    let params: Params = runner.params();
+    let codec = params.codec().unwrap();
     let lines = get_file_contents("data/input.txt")?;
+    let seats = codec.decode_all(&lines).unwrap_or_else(|e| panic!("{}", e));
 
-    println!("Day 5 / Part 1: {}", get_max_id(&lines));
-    println!("Day 5 / Part 2: {}", find_missing_seat(&lines));
-
-    Ok(())
-}
+    runner.part(1, || get_max_id(&seats));
+    runner.part(2, || {
+        find_missing_seat(&codec, &seats).expect("No free seat between two taken ones")
+    });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_seat_row() {
-        assert_eq!(70, seat_row("BFFFBBFRRR"));
-        assert_eq!(14, seat_row("FFFBBBFRRR"));
-        assert_eq!(102, seat_row("BBFFBBFRLL"));
+    if params.seat_map {
+        println!("{}", SeatMap::new(&codec, &seats));
     }
 
-    #[test]
-    fn test_seat_col() {
-        assert_eq!(7, seat_col("BFFFBBFRRR"));
-        assert_eq!(7, seat_col("FFFBBBFRRR"));
-        assert_eq!(4, seat_col("BBFFBBFRLL"));
-    }
+    runner.finish();
 
-    #[test]
-    fn test_seat_id() {
-        assert_eq!(567, seat_id("BFFFBBFRRR"));
-        assert_eq!(119, seat_id("FFFBBBFRRR"));
-        assert_eq!(820, seat_id("BBFFBBFRLL"));
-    }
+    Ok(())
 }
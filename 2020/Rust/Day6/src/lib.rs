@@ -0,0 +1,141 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/6
+
+use std::collections::BTreeSet;
+
+pub mod survey;
+
+pub use survey::{Group, Query, Survey};
+
+
+fn individual_answers(answers: &str) -> BTreeSet<char> {
+    answers.chars().collect()
+}
+
+pub fn count_total_answers(groups: &[Vec<String>]) -> usize {
+    Survey::new(groups).count(Query::Any)
+}
+
+pub fn count_total_all_yes(groups: &[Vec<String>]) -> usize {
+    Survey::new(groups).count(Query::All)
+}
+
+pub fn parse_groups(lines: &[String]) -> Vec<Vec<String>> {
+    lines
+        .split(|line| line.is_empty())
+        .map(|x| x.to_vec())
+        .collect()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let groups = parse_groups(lines);
+
+    match part {
+        1 => Some(count_total_answers(&groups).to_string()),
+        2 => Some(count_total_all_yes(&groups).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 6, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proper_vec(v: Vec<&str>) -> Vec<String> {
+        v.iter().map(|&x| String::from(x)).collect()
+    }
+
+    fn count_group_answers(group: &[String]) -> usize {
+        Group::new(group).count(Query::Any)
+    }
+
+    fn count_group_all_yes(group: &[String]) -> usize {
+        Group::new(group).count(Query::All)
+    }
+
+    fn answers(s: &str) -> Vec<char> {
+        individual_answers(s).into_iter().collect()
+    }
+
+    #[test]
+    fn test_individual_answers() {
+        assert_eq!(vec!['a', 'b', 'c'], answers("abc"));
+        assert_eq!(vec!['a'], answers("aaaa"));
+        assert_eq!(vec!['b', 'c', 'm', 't', 'y'], answers("cbymt"));
+        assert_eq!(vec!['A', 'a', '{', 'é'], answers("{Aaé"));
+        assert_eq!(vec!['a', '\u{10FFFF}'], answers("\u{10FFFF}a"));
+    }
+
+    #[test]
+    fn test_count_group_answers() {
+        assert_eq!(3, count_group_answers(&proper_vec(vec!["abc"])));
+        assert_eq!(3, count_group_answers(&proper_vec(vec!["a", "b", "c"])));
+        assert_eq!(3, count_group_answers(&proper_vec(vec!["ab", "ac"])));
+        assert_eq!(1, count_group_answers(&proper_vec(vec!["a", "a", "a", "a"])));
+        assert_eq!(1, count_group_answers(&proper_vec(vec!["b"])));
+    }
+
+    #[test]
+    fn test_count_total_answers() {
+        assert_eq!(
+            11,
+            count_total_answers(
+                &[
+                    proper_vec(vec!["abc"]),
+                    proper_vec(vec!["a", "b", "c"]),
+                    proper_vec(vec!["ab", "ac"]),
+                    proper_vec(vec!["a", "a", "a", "a"]),
+                    proper_vec(vec!["b"]),
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn test_count_group_all_yes() {
+        assert_eq!(3, count_group_all_yes(&proper_vec(vec!["abc"])));
+        assert_eq!(0, count_group_all_yes(&proper_vec(vec!["a", "b", "c"])));
+        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["ab", "ac"])));
+        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["a", "a", "a", "a"])));
+        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["b"])));
+    }
+
+    #[test]
+    fn test_count_total_all_yes() {
+        assert_eq!(
+            6,
+            count_total_all_yes(
+                &[
+                    proper_vec(vec!["abc"]),
+                    proper_vec(vec!["a", "b", "c"]),
+                    proper_vec(vec!["ab", "ac"]),
+                    proper_vec(vec!["a", "a", "a", "a"]),
+                    proper_vec(vec!["b"]),
+                ],
+            ),
+        );
+    }
+}
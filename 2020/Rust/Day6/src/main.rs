@@ -24,124 +24,51 @@
 
 #![allow(non_snake_case)]
 
+use aoc::config;
 use aoc::fs::get_file_contents;
+use aoc::runner::Runner;
+use day6::{count_total_all_yes, count_total_answers, parse_groups, Query, Survey};
+use serde::Deserialize;
 
 
-fn individual_answers(answers: &str) -> u32 {
-    answers.bytes().fold(0, |acc, c| {
-        acc | 0x1 << (c - b'a')
-    })
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    // Prints how many questions match, and in how many groups each one does
+    query: Option<String>,
 }
 
-fn count_group_answers(group: &Vec<String>) -> usize {
-    group.iter().fold(0_u32, |acc, answers| {
-        acc | individual_answers(answers)
-    }).count_ones() as usize
-}
-
-fn count_total_answers(groups: &Vec<Vec<String>>) -> usize {
-    groups.iter().fold(0, |acc, group| {
-        acc + count_group_answers(group)
-    })
-}
-
-fn count_group_all_yes(group: &Vec<String>) -> usize {
-    group
-        .iter()
-        .fold([0; 26], |mut acc, person| {
-            for c in person.bytes() {
-                acc[(c - b'a') as usize] += 1;
-            }
-            acc
-        })
-        .iter()
-        .filter(|a| **a == group.len())
-        .count()
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        match &self.query {
+            None => Ok(()),
+            Some(q) => q.parse::<Query>().map(|_| ()),
+        }
+    }
 }
 
-fn count_total_all_yes(groups: &Vec<Vec<String>>) -> usize {
-    groups.iter().fold(0, |acc, group| {
-        acc + count_group_all_yes(group)
-    })
+fn print_histogram(survey: &Survey, query: Query) {
+    println!("Questions where {} answered yes: {}", query, survey.count(query));
+    for (question, groups) in survey.histogram(query).iter() {
+        println!("    {}: {} groups", question, groups);
+    }
 }
 
 fn main() -> std::io::Result<()> {
-    let mut lines = get_file_contents("data/input.txt")?;
-    let groups: Vec<Vec<String>> =
-        lines
-        .as_mut_slice()
-        .split(|line| line.is_empty())
-        .map(|x| x.to_vec())
-        .collect();
-
-    println!("Day 6 / Part 1: {}", count_total_answers(&groups));
-    println!("Day 6 / Part 2: {}", count_total_all_yes(&groups));
-
-    Ok(())
-}
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(6);
+    let params: Params = runner.params();
+    let lines = get_file_contents("data/input.txt")?;
+    let groups = parse_groups(&lines);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    runner.part(1, || count_total_answers(&groups));
+    runner.part(2, || count_total_all_yes(&groups));
 
-    fn proper_vec(v: Vec<&str>) -> Vec<String> {
-        v.iter().map(|&x| String::from(x)).collect()
+    if let Some(query) = &params.query {
+        print_histogram(&Survey::new(&groups), query.parse().unwrap());
     }
 
-    #[test]
-    fn test_individual_answers() {
-        assert_eq!(0x7, individual_answers("abc"));
-        assert_eq!(0x1, individual_answers("aaaa"));
-        assert_eq!(0x1081006, individual_answers("cbymt"));
-    }
-
-    #[test]
-    fn test_count_group_answers() {
-        assert_eq!(3, count_group_answers(&proper_vec(vec!["abc"])));
-        assert_eq!(3, count_group_answers(&proper_vec(vec!["a", "b", "c"])));
-        assert_eq!(3, count_group_answers(&proper_vec(vec!["ab", "ac"])));
-        assert_eq!(1, count_group_answers(&proper_vec(vec!["a", "a", "a", "a"])));
-        assert_eq!(1, count_group_answers(&proper_vec(vec!["b"])));
-    }
-
-    #[test]
-    fn test_count_total_answers() {
-        assert_eq!(
-            11,
-            count_total_answers(
-                &vec![
-                    proper_vec(vec!["abc"]),
-                    proper_vec(vec!["a", "b", "c"]),
-                    proper_vec(vec!["ab", "ac"]),
-                    proper_vec(vec!["a", "a", "a", "a"]),
-                    proper_vec(vec!["b"]),
-                ],
-            ),
-        );
-    }
+    runner.finish();
 
-    #[test]
-    fn test_count_group_all_yes() {
-        assert_eq!(3, count_group_all_yes(&proper_vec(vec!["abc"])));
-        assert_eq!(0, count_group_all_yes(&proper_vec(vec!["a", "b", "c"])));
-        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["ab", "ac"])));
-        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["a", "a", "a", "a"])));
-        assert_eq!(1, count_group_all_yes(&proper_vec(vec!["b"])));
-    }
-
-    #[test]
-    fn test_count_total_all_yes() {
-        assert_eq!(
-            6,
-            count_total_all_yes(
-                &vec![
-                    proper_vec(vec!["abc"]),
-                    proper_vec(vec!["a", "b", "c"]),
-                    proper_vec(vec!["ab", "ac"]),
-                    proper_vec(vec!["a", "a", "a", "a"]),
-                    proper_vec(vec!["b"]),
-                ],
-            ),
-        );
-    }
+    Ok(())
 }
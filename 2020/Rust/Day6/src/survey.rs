@@ -0,0 +1,207 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/6
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::individual_answers;
+
+
+/// Which questions of a group count, by how many of its people answered
+/// yes to them. Only the questions someone in the group answered are looked
+/// at, since there's no telling which questions exist otherwise.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum Query {
+    /// Anyone answered yes.
+    Any,
+    /// Everyone answered yes.
+    All,
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Query {
+    pub fn matches(&self, yes: usize, people: usize) -> bool {
+        match *self {
+            Query::Any => yes > 0,
+            Query::All => yes == people,
+            Query::Exactly(k) => yes == k,
+            Query::AtLeast(k) => yes >= k,
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    /// `any`, `all`, `exactly K` or `at least K`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let k = |w: &str| usize::from_str(w).map_err(|_| format!("Invalid count in query: {}", s));
+
+        match words[..] {
+            ["any"] => Ok(Query::Any),
+            ["all"] => Ok(Query::All),
+            ["exactly", n] => Ok(Query::Exactly(k(n)?)),
+            ["at", "least", n] => Ok(Query::AtLeast(k(n)?)),
+            _ => Err(format!("Unknown query {:?}, expected any, all, exactly K or at least K", s)),
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Query::Any => write!(f, "any"),
+            Query::All => write!(f, "all"),
+            Query::Exactly(k) => write!(f, "exactly {}", k),
+            Query::AtLeast(k) => write!(f, "at least {}", k),
+        }
+    }
+}
+
+/// How many people of a group answered yes to each question.
+#[derive(Clone,Debug,PartialEq)]
+pub struct Group {
+    people: usize,
+    yes: BTreeMap<char, usize>,
+}
+
+impl Group {
+    pub fn new(people: &[String]) -> Self {
+        let mut yes = BTreeMap::new();
+
+        for answers in people.iter() {
+            for c in individual_answers(answers) {
+                *yes.entry(c).or_insert(0) += 1;
+            }
+        }
+
+        Group { people: people.len(), yes }
+    }
+
+    pub fn people(&self) -> usize {
+        self.people
+    }
+
+    pub fn questions(&self, query: Query) -> impl Iterator<Item = char> + '_ {
+        self.yes
+            .iter()
+            .filter(move |&(_, &yes)| query.matches(yes, self.people))
+            .map(|(&c, _)| c)
+    }
+
+    pub fn count(&self, query: Query) -> usize {
+        self.questions(query).count()
+    }
+}
+
+#[derive(Clone,Debug,PartialEq)]
+pub struct Survey {
+    groups: Vec<Group>,
+}
+
+impl Survey {
+    pub fn new(groups: &[Vec<String>]) -> Self {
+        Survey { groups: groups.iter().map(|g| Group::new(g)).collect() }
+    }
+
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// The sum, over the groups, of their questions that match.
+    pub fn count(&self, query: Query) -> usize {
+        self.groups.iter().map(|g| g.count(query)).sum()
+    }
+
+    /// In how many groups each question matches.
+    pub fn histogram(&self, query: Query) -> BTreeMap<char, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for c in self.groups.iter().flat_map(|g| g.questions(query)) {
+            *histogram.entry(c).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn survey() -> Survey {
+        let groups: Vec<Vec<String>> = [
+            vec!["abc"],
+            vec!["a", "b", "c"],
+            vec!["ab", "ac"],
+            vec!["a", "a", "a", "a"],
+            vec!["b"],
+        ]
+        .iter()
+        .map(|g| g.iter().map(|&p| String::from(p)).collect())
+        .collect();
+
+        Survey::new(&groups)
+    }
+
+    #[test]
+    fn test_queries() {
+        let s = survey();
+
+        assert_eq!(11, s.count(Query::Any));
+        assert_eq!(6, s.count(Query::All));
+        assert_eq!(9, s.count(Query::Exactly(1)));
+        assert_eq!(2, s.count(Query::AtLeast(2)));
+        assert_eq!(
+            vec![('a', 4), ('b', 4), ('c', 3)],
+            s.histogram(Query::Any).into_iter().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            vec![('a', 3), ('b', 2), ('c', 1)],
+            s.histogram(Query::All).into_iter().collect::<Vec<_>>(),
+        );
+        assert_eq!(vec!['a'], s.groups()[2].questions(Query::Exactly(2)).collect::<Vec<char>>());
+    }
+
+    #[test]
+    fn test_other_alphabets() {
+        let group = Group::new(&["éü€ü".to_string(), "€Z".to_string()]);
+
+        assert_eq!(2, group.people());
+        assert_eq!(vec!['€'], group.questions(Query::All).collect::<Vec<char>>());
+        assert_eq!(4, group.count(Query::Any));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        for q in [Query::Any, Query::All, Query::Exactly(2), Query::AtLeast(3)].iter() {
+            assert_eq!(Ok(*q), q.to_string().parse());
+        }
+        assert!("at most 2".parse::<Query>().is_err());
+        assert!("exactly two".parse::<Query>().is_err());
+    }
+}
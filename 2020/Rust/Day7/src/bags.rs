@@ -0,0 +1,260 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/7
+
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+
+use aoc::numeric::Number;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+
+/// A bag colour, numbered in the order it shows up in the rules. It only
+/// means something to the `BagRules` it came from: with any other rules it
+/// names a different bag, so counts come out wrong, or it panics when it's
+/// out of range.
+#[derive(Clone,Copy,Debug,Deserialize,Eq,Hash,Ord,PartialEq,PartialOrd,Serialize)]
+pub struct Color(usize);
+
+/// What each colour of bag holds, parsed once and looked up both ways.
+#[derive(Clone,Debug,Deserialize,Serialize)]
+pub struct BagRules {
+    names: Vec<String>,
+    ids: HashMap<String, Color>,
+    // How many bags of each colour a bag holds directly
+    contents: Vec<Vec<(usize, Color)>>,
+    // Colours of the bags that hold a bag directly
+    holders: Vec<Vec<Color>>,
+    // Every bag comes after the bags that hold it
+    order: Vec<Color>,
+}
+
+impl BagRules {
+    pub fn new(rules: &[String]) -> Result<Self, String> {
+        lazy_static! {
+            static ref RULE: Regex = Regex::new(r"^(.+?) bags contain (.+)\.$").unwrap();
+            static ref CONTENT: Regex = Regex::new(r"^(\d+) (.+?) bags?$").unwrap();
+        }
+
+        let mut bags = BagRules {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            contents: Vec::new(),
+            holders: Vec::new(),
+            order: Vec::new(),
+        };
+        let mut has_rule = Vec::new();
+
+        for rule in rules.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+            let c = RULE.captures(rule).ok_or_else(|| format!("Malformed rule: {}", rule))?;
+            let outer = bags.intern(&c[1]);
+
+            has_rule.resize(bags.names.len(), false);
+            if has_rule[outer.0] {
+                return Err(format!("More than one rule for {} bags", &c[1]));
+            }
+            has_rule[outer.0] = true;
+
+            if &c[2] == "no other bags" {
+                continue;
+            }
+
+            for content in c[2].split(", ") {
+                let cc = CONTENT.captures(content).ok_or_else(|| format!("Malformed rule: {}", rule))?;
+                let n = usize::from_str(&cc[1]).map_err(|_| format!("Malformed rule: {}", rule))?;
+                let inner = bags.intern(&cc[2]);
+
+                bags.contents[outer.0].push((n, inner));
+                bags.holders[inner.0].push(outer);
+            }
+        }
+
+        bags.order = bags.sort()?;
+        Ok(bags)
+    }
+
+    fn intern(&mut self, name: &str) -> Color {
+        if let Some(&color) = self.ids.get(name) {
+            return color;
+        }
+
+        let color = Color(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), color);
+        self.contents.push(Vec::new());
+        self.holders.push(Vec::new());
+        color
+    }
+
+    // The bags from the outermost in, or the colours of a loop of bags that
+    // end up holding themselves.
+    fn sort(&self) -> Result<Vec<Color>, String> {
+        let mut holders: Vec<usize> = self.holders.iter().map(|h| h.len()).collect();
+        let mut ready: VecDeque<Color> = (0..self.names.len())
+            .filter(|&i| holders[i] == 0)
+            .map(Color)
+            .collect();
+        let mut order = Vec::new();
+
+        while let Some(color) = ready.pop_front() {
+            order.push(color);
+            for &(_, inner) in self.contents[color.0].iter() {
+                holders[inner.0] -= 1;
+                if holders[inner.0] == 0 {
+                    ready.push_back(inner);
+                }
+            }
+        }
+
+        if order.len() == self.names.len() {
+            return Ok(order);
+        }
+
+        // Whatever's left is held by something else that's left, so going
+        // from holder to holder runs into a loop.
+        let mut seen = vec![None; self.names.len()];
+        let mut path = Vec::new();
+        let mut color = (0..self.names.len()).map(Color).find(|c| holders[c.0] > 0).unwrap();
+
+        while seen[color.0].is_none() {
+            seen[color.0] = Some(path.len());
+            path.push(color);
+            color = *self.holders[color.0].iter().find(|h| holders[h.0] > 0).unwrap();
+        }
+
+        let mut cycle: Vec<&str> = path[seen[color.0].unwrap()..]
+            .iter()
+            .rev()
+            .map(|&c| self.name(c))
+            .collect();
+        cycle.push(cycle[0]);
+
+        Err(format!("Bags that end up holding themselves: {}", cycle.join(" -> ")))
+    }
+
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.ids.get(name).cloned()
+    }
+
+    pub fn name(&self, color: Color) -> &str {
+        &self.names[color.0]
+    }
+
+    pub fn contents(&self, color: Color) -> &[(usize, Color)] {
+        &self.contents[color.0]
+    }
+
+    pub fn holders(&self, color: Color) -> &[Color] {
+        &self.holders[color.0]
+    }
+
+    /// Every colour of bag that ends up holding the bag, in no particular
+    /// order.
+    pub fn containers(&self, color: Color) -> Vec<Color> {
+        let mut seen = vec![false; self.names.len()];
+        let mut pending = vec![color];
+        let mut containers = Vec::new();
+
+        while let Some(c) = pending.pop() {
+            for &h in self.holders(c).iter() {
+                if !seen[h.0] {
+                    seen[h.0] = true;
+                    containers.push(h);
+                    pending.push(h);
+                }
+            }
+        }
+
+        containers
+    }
+
+    /// How many bags the bag holds, counting the ones inside other bags.
+    /// Each colour is only counted once, from the innermost bags out.
+    pub fn bags_inside<N: Number>(&self, color: Color) -> N {
+        let mut totals: Vec<N> = vec![N::zero(); self.names.len()];
+
+        for &c in self.order.iter().rev() {
+            totals[c.0] = self.contents(c).iter().fold(N::zero(), |acc, &(n, inner)| {
+                let n = N::from_count(n);
+                acc + n.clone() + n * totals[inner.0].clone()
+            });
+
+            if c == color {
+                break;
+            }
+        }
+
+        totals[color.0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(lines: &[&str]) -> Result<BagRules, String> {
+        BagRules::new(&lines.iter().map(|&l| String::from(l)).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn test_cycles() {
+        let err = rules(&[
+            "light red bags contain 1 dark blue bag.",
+            "dark blue bags contain 2 bright white bags, 1 faded blue bag.",
+            "bright white bags contain 1 light red bag.",
+            "faded blue bags contain no other bags.",
+        ]).unwrap_err();
+
+        assert!(
+            [
+                "light red -> dark blue -> bright white -> light red",
+                "dark blue -> bright white -> light red -> dark blue",
+                "bright white -> light red -> dark blue -> bright white",
+            ].iter().any(|cycle| err.ends_with(cycle)),
+            "{}", err,
+        );
+        assert!(rules(&["shiny gold bags contain 1 shiny gold bag."]).is_err());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(
+            Err("Malformed rule: shiny gold bags contain some dark red bags.".to_string()),
+            rules(&["shiny gold bags contain some dark red bags."]).map(|_| ()),
+        );
+        assert!(rules(&["a bags contain no other bags.", "a bags contain 1 b bag."]).is_err());
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        let lines: Vec<String> = (0..100_000)
+            .map(|i| format!("c{} bags contain 1 c{} bag.", i, i + 1))
+            .collect();
+        let bags = BagRules::new(&lines).unwrap();
+
+        assert_eq!(100_000, bags.bags_inside::<usize>(bags.color("c0").unwrap()));
+        assert_eq!(100_000, bags.containers(bags.color("c100000").unwrap()).len());
+    }
+}
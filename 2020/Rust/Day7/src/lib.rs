@@ -0,0 +1,117 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/7
+
+use aoc::numeric::{Number, Wide};
+
+pub mod bags;
+
+use bags::{BagRules, Color};
+
+
+pub fn total_containing_bags(rules: &BagRules, color: Color) -> usize {
+    rules.containers(color).len()
+}
+
+pub fn total_contained_bags<N: Number>(rules: &BagRules, color: Color) -> N {
+    rules.bags_inside(color)
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let rules = BagRules::new(lines).unwrap_or_else(|e| panic!("{}", e));
+    let color = rules.color("shiny gold").expect("No rule mentions shiny gold bags");
+
+    match part {
+        1 => Some(total_containing_bags(&rules, color).to_string()),
+        2 => Some(total_contained_bags::<Wide<usize>>(&rules, color).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 7, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: [&str; 9] = [
+        "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+        "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
+        "bright white bags contain 1 shiny gold bag.",
+        "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
+        "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
+        "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
+        "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
+        "faded blue bags contain no other bags.",
+        "dotted black bags contain no other bags.",
+    ];
+
+    const RULES2: [&str; 7] = [
+        "shiny gold bags contain 2 dark red bags.",
+        "dark red bags contain 2 dark orange bags.",
+        "dark orange bags contain 2 dark yellow bags.",
+        "dark yellow bags contain 2 dark green bags.",
+        "dark green bags contain 2 dark blue bags.",
+        "dark blue bags contain 2 dark violet bags.",
+        "dark violet bags contain no other bags.",
+    ];
+
+    fn bag_rules(v: &[&str]) -> BagRules {
+        BagRules::new(&v.iter().map(|&x| String::from(x)).collect::<Vec<String>>()).unwrap()
+    }
+
+    fn shiny_gold(rules: &BagRules) -> Color {
+        rules.color("shiny gold").unwrap()
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let rules = bag_rules(&RULES);
+        let holders: Vec<&str> = rules.holders(shiny_gold(&rules)).iter().map(|&c| rules.name(c)).collect();
+        let contents: Vec<(usize, &str)> = rules
+            .contents(shiny_gold(&rules))
+            .iter()
+            .map(|&(n, c)| (n, rules.name(c)))
+            .collect();
+
+        assert_eq!(vec!["bright white", "muted yellow"], holders);
+        assert_eq!(vec![(1, "dark olive"), (2, "vibrant plum")], contents);
+        assert_eq!(None, rules.color("plaid magenta"));
+    }
+
+    #[test]
+    fn test_total_containing_bags() {
+        let rules = bag_rules(&RULES);
+
+        assert_eq!(4, total_containing_bags(&rules, shiny_gold(&rules)));
+    }
+
+    #[test]
+    fn test_total_contained_bags() {
+        let rules = bag_rules(&RULES);
+        let rules2 = bag_rules(&RULES2);
+
+        assert_eq!(32, total_contained_bags::<usize>(&rules, shiny_gold(&rules)));
+        assert_eq!(126, total_contained_bags::<usize>(&rules2, shiny_gold(&rules2)));
+    }
+}
@@ -22,167 +22,50 @@
 
 // https://adventofcode.com/2020/day/7
 
-use std::collections::{HashMap, HashSet};
-use std::str::FromStr;
-
+use aoc::config;
 use aoc::fs::get_file_contents;
-use lazy_static::lazy_static;
-use regex::Regex;
-
-fn build_graph(rules: &Vec<String>) -> HashMap<String, HashSet<String>> {
-    lazy_static! {
-        static ref CONTAINS: Regex = Regex::new(r"(.*) bags? contain (.*)").unwrap();
-        static ref CONTAINED: Regex = Regex::new(r"(\d+) (.*) bags?").unwrap();
-    }
+use aoc::numeric::Wide;
+use aoc::runner::Runner;
+use day7::bags::BagRules;
+use day7::{total_contained_bags, total_containing_bags};
+use serde::Deserialize;
 
-    let mut graph = HashMap::new();
 
-    for r in rules.iter() {
-        if let Some(g1) = CONTAINS.captures(r) {
-            let _contains = g1.get(1).unwrap().as_str();
-            let _contained: Vec<&str> = g1.get(2).unwrap().as_str().split(",").collect();
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    color: String,
+}
 
-            for c in _contained.iter() {
-                if let Some(g2) = CONTAINED.captures(c) {
-                    graph.entry(String::from(g2.get(2).unwrap().as_str()))
-                        .or_insert(HashSet::new())
-                        .insert(String::from(_contains));
-                }
-            }
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            color: "shiny gold".to_string(),
         }
     }
-
-    graph
-}
-
-fn contain_color(graph: &HashMap<String, HashSet<String>>, color: &str, acc: &mut HashSet<String>) {
-    match graph.get(color) {
-        None => (),
-        Some(contain) => {
-            contain.iter().for_each(|c| {
-                acc.insert(String::from(c));
-                contain_color(graph, c, acc);
-            });
-        },
-    };
-}
-
-fn total_containing_bags(rules: &Vec<String>, color: &str) -> usize {
-    let graph = build_graph(&rules);
-    let mut acc = HashSet::new();
-    contain_color(&graph, color, &mut acc);
-
-    acc.len()
 }
 
-fn build_inverted_graph(rules: &Vec<String>) -> HashMap<String, HashSet<(usize, String)>> {
-    lazy_static! {
-        static ref CONTAINS: Regex = Regex::new(r"(.*) bags? contain (.*)").unwrap();
-        static ref CONTAINED: Regex = Regex::new(r"(\d+) (.*) bags?").unwrap();
-    }
-
-    let mut graph = HashMap::new();
-
-    for r in rules.iter() {
-        if let Some(g1) = CONTAINS.captures(r) {
-            let _contains = g1.get(1).unwrap().as_str();
-            let _contained: Vec<&str> = g1.get(2).unwrap().as_str().split(",").collect();
-
-            for c in _contained.iter() {
-                if let Some(g2) = CONTAINED.captures(c) {
-                    graph.entry(String::from(_contains))
-                        .or_insert(HashSet::new())
-                        .insert((
-                            usize::from_str(g2.get(1).unwrap().as_str()).unwrap(),
-                            String::from(g2.get(2).unwrap().as_str())
-                        ));
-                }
-            }
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.color.trim().is_empty() {
+            return Err("color can't be empty".to_string());
         }
-    }
-
-    graph
-}
 
-fn contained_colors(graph: &HashMap<String, HashSet<(usize,String)>>, color: &str) -> usize {
-    match graph.get(color) {
-        None => 0,
-        Some(contained) => contained.iter().fold(0, |acc, (c, i)| {
-            acc + c + c * contained_colors(graph, i)
-        })
+        Ok(())
     }
 }
 
-fn total_contained_bags(rules: &Vec<String>, color: &str) -> usize {
-    let graph = build_inverted_graph(&rules);
-    contained_colors(&graph, color)
-}
-
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(7);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
+    let rules = runner.parsed("rules", || BagRules::new(&lines).unwrap_or_else(|e| panic!("{}", e)));
+    let color = rules.color(&params.color).unwrap_or_else(|| panic!("No rule mentions {} bags", params.color));
 
-    println!("Day 7 / part 1: {}", total_containing_bags(&lines, "shiny gold"));
-    println!("Day 7 / part 2: {}", total_contained_bags(&lines, "shiny gold"));
+    runner.part(1, || total_containing_bags(&rules, color));
+    runner.part(2, || total_contained_bags::<Wide<usize>>(&rules, color));
+    runner.finish();
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const RULES: [&str; 9] = [
-        "light red bags contain 1 bright white bag, 2 muted yellow bags.",
-        "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
-        "bright white bags contain 1 shiny gold bag.",
-        "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
-        "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
-        "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
-        "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
-        "faded blue bags contain no other bags.",
-        "dotted black bags contain no other bags.",
-    ];
-
-    const RULES2: [&str; 7] = [
-        "shiny gold bags contain 2 dark red bags.",
-        "dark red bags contain 2 dark orange bags.",
-        "dark orange bags contain 2 dark yellow bags.",
-        "dark yellow bags contain 2 dark green bags.",
-        "dark green bags contain 2 dark blue bags.",
-        "dark blue bags contain 2 dark violet bags.",
-        "dark violet bags contain no other bags.",
-    ];
-
-    fn proper_vec(v: Vec<&str>) -> Vec<String> {
-        v.iter().map(|&x| String::from(x)).collect()
-    }
-
-    #[test]
-    fn test_build_graph() {
-        let graph = build_graph(&proper_vec(RULES.to_vec().clone()));
-
-        assert!(graph.contains_key("shiny gold"));
-        assert!(graph.get("shiny gold").unwrap().contains("bright white"));
-        assert!(graph.get("shiny gold").unwrap().contains("muted yellow"));
-    }
-
-    #[test]
-    fn test_total_containing_bags() {
-        assert_eq!(
-            4,
-            total_containing_bags(&proper_vec(RULES.to_vec().clone()), "shiny gold"),
-        );
-    }
-
-    #[test]
-    fn test_total_contained_bags() {
-        assert_eq!(
-            32,
-            total_contained_bags(&proper_vec(RULES.to_vec().clone()), "shiny gold"),
-        );
-        assert_eq!(
-            126,
-            total_contained_bags(&proper_vec(RULES2.to_vec().clone()), "shiny gold"),
-        );
-    }
-}
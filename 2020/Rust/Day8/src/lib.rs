@@ -0,0 +1,167 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/8
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc::explain;
+
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    NOP,
+    JMP,
+    ACC,
+}
+
+#[derive(Default)]
+pub struct Program {
+    acc: i32,
+    pc: i32,
+    visited: HashSet<i32>,
+    code: Vec<(Instruction, i32)>,
+}
+
+impl Program {
+    pub fn new(code: &[(Instruction, i32)]) -> Self {
+        Program {
+            code: code.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn execute_instruction(&mut self) -> Result<(), i32> {
+        use Instruction::*;
+
+        match self.visited.get(&self.pc) {
+            Some(_) => Err(self.acc),
+            None => {
+                self.visited.insert(self.pc);
+
+                match &self.code[self.pc as usize] {
+                    (NOP, _) => self.pc += 1,
+                    (JMP, n) => self.pc += n,
+                    (ACC, n) => {
+                        self.acc += n;
+                        self.pc += 1;
+                    },
+                };
+
+                Ok(())
+            },
+        }
+    }
+
+    pub fn execute(&mut self) -> Result<i32, i32> {
+        while (self.pc as usize) < self.code.len() {
+            self.execute_instruction()?;
+        }
+
+        Ok(self.acc)
+    }
+}
+
+pub fn fix_and_execute(code: &[(Instruction, i32)]) -> i32 {
+    use Instruction::*;
+
+    for i in 0..code.len() {
+        if code[i].0 == ACC { continue; }
+
+        let mut new_code = code.to_vec();
+        new_code[i] = (
+            if code[i].0 == NOP { JMP } else { NOP },
+            code[i].1,
+        );
+
+        let mut p = Program::new(&new_code);
+        if let Ok(n) = p.execute() {
+            explain!(
+                "fix", "flipped {:?} {} at line {} into {:?}",
+                code[i].0, code[i].1, i + 1, new_code[i].0,
+            );
+            return n;
+        }
+    }
+
+    unreachable!();
+}
+
+pub fn parse_code(lines: Vec<String>) -> Vec<(Instruction, i32)> {
+    lines
+        .iter()
+        .map(|line| {
+            let s: Vec<&str> = line.split(" ").collect();
+            let op = match s[0] {
+                "nop" => Instruction::NOP,
+                "jmp" => Instruction::JMP,
+                "acc" => Instruction::ACC,
+                _ => panic!("Unknown instruction"),
+            };
+
+            (op, i32::from_str(s[1]).unwrap())
+        })
+        .collect()
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let code = parse_code(lines.to_vec());
+
+    match part {
+        1 => Some(format!("{:?}", Program::new(&code).execute())),
+        2 => Some(fix_and_execute(&code).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 8, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: [(Instruction, i32); 9] = [
+        (Instruction::NOP, 0),
+        (Instruction::ACC, 1),
+        (Instruction::JMP, 4),
+        (Instruction::ACC, 3),
+        (Instruction::JMP, -3),
+        (Instruction::ACC, -99),
+        (Instruction::ACC, 1),
+        (Instruction::JMP, -4),
+        (Instruction::ACC, 6),
+    ];
+
+    #[test]
+    fn test_execute() {
+        let mut p = Program::new(&CODE);
+
+        assert_eq!(Err(5), p.execute());
+    }
+
+    #[test]
+    fn test_fix_and_execute() {
+        assert_eq!(8, fix_and_execute(&CODE));
+    }
+}
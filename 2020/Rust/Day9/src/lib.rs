@@ -0,0 +1,180 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// https://adventofcode.com/2020/day/9
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use aoc::explain;
+use aoc::math::two_sum;
+
+
+fn find_incorrect(numbers: &[i64], preamble: usize) -> Option<usize> {
+    let i = (preamble..numbers.len())
+        .find(|&i| two_sum(&numbers[i-preamble..i], numbers[i]).is_none())?;
+
+    explain!(
+        "invalid", "{} at index {} is not the sum of two of the previous {}",
+        numbers[i], i, preamble,
+    );
+    Some(i)
+}
+
+
+pub fn crack_xmas(numbers: &[i64], preamble: usize) -> i64 {
+    match find_incorrect(numbers, preamble) {
+        None => panic!("Uncrackable!"),
+        Some(i) => numbers[i],
+    }
+}
+
+fn find_min_max(numbers: &[i64], start: usize, end: usize, t: i64) -> Option<(i64, i64)> {
+    let mut _min = i64::MAX;
+    let mut _max = i64::MIN;
+    let mut sum = 0_i64;
+
+    for (j, n) in numbers[start..end].iter().enumerate() {
+        sum += n;
+        _min = _min.min(*n);
+        _max = _max.max(*n);
+
+        match sum.cmp(&t) {
+            Ordering::Equal => {
+                explain!("range", "indices {}..={} add up to {}", start, start + j, t);
+                return Some((_min, _max));
+            },
+            Ordering::Greater => break,
+            Ordering::Less => (),
+        }
+    }
+
+    None
+}
+
+pub fn crack_me(numbers: &[i64], index: usize) -> i64 {
+    let target_sum = numbers[index];
+
+    for i in 0..index {
+        if numbers[i] >= target_sum { continue; }
+
+        if let Some((_min, _max)) = find_min_max(numbers, i, index, target_sum) {
+            return _min + _max;
+        }
+    }
+
+    unreachable!();
+}
+
+// Slides a window of at least two numbers over the prefix sums, instead of
+// adding up every range again. It relies on all the numbers being positive,
+// so that growing the window can only increase its sum.
+pub fn crack_me_prefix_sums(numbers: &[i64], index: usize) -> i64 {
+    let target_sum = numbers[index];
+    let mut prefix = vec![0_i64; index+1];
+
+    for i in 0..index {
+        prefix[i+1] = prefix[i] + numbers[i];
+    }
+
+    let (mut lo, mut hi) = (0, 2);
+    while hi <= index {
+        match (prefix[hi] - prefix[lo]).cmp(&target_sum) {
+            Ordering::Equal => {
+                explain!("range", "indices {}..={} add up to {}", lo, hi - 1, target_sum);
+                let range = &numbers[lo..hi];
+                return range.iter().min().unwrap() + range.iter().max().unwrap();
+            },
+            Ordering::Less => hi += 1,
+            Ordering::Greater => {
+                lo += 1;
+                hi = hi.max(lo + 2);
+            },
+        }
+    }
+
+    unreachable!();
+}
+
+pub fn crack_xmas2(numbers: &[i64], preamble: usize, crack: fn(&[i64], usize) -> i64) -> i64 {
+    let i = match find_incorrect(numbers, preamble) {
+        None => panic!("Uncrackable!"),
+        Some(n) => n,
+    };
+
+    crack(numbers, i)
+}
+
+fn solve(part: u8, lines: &[String]) -> Option<String> {
+    let numbers: Vec<i64> = lines
+        .iter()
+        .map(|line| i64::from_str(line).unwrap())
+        .collect();
+
+    match part {
+        1 => Some(crack_xmas(&numbers, 25).to_string()),
+        2 => Some(crack_xmas2(&numbers, 25, crack_me_prefix_sums).to_string()),
+        _ => None,
+    }
+}
+
+aoc::plugin!(day: 9, parts: 2, solve: solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMBERS: [i64; 20] = [
+        35,  20,  15,  25,  47,
+        40,  62,  55,  65,  95,
+        102, 117, 150, 182, 127,
+        219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn test_crack_xmas() {
+        assert_eq!(
+            127,
+            crack_xmas(&NUMBERS, 5),
+        );
+    }
+
+    #[test]
+    fn test_crack_xmas2() {
+        assert_eq!(
+            62,
+            crack_xmas2(&NUMBERS, 5, crack_me),
+        );
+        assert_eq!(
+            62,
+            crack_xmas2(&NUMBERS, 5, crack_me_prefix_sums),
+        );
+    }
+
+    #[test]
+    fn test_range_ends_with_min_or_max() {
+        for numbers in [[1, 2, 10, 13], [10, 2, 1, 13]].iter() {
+            assert_eq!(11, crack_me(numbers, 3));
+            assert_eq!(11, crack_me_prefix_sums(numbers, 3));
+        }
+    }
+}
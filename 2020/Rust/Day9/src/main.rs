@@ -24,106 +24,55 @@
 
 #![allow(non_snake_case)]
 
-use std::cmp::Ordering;
 use std::str::FromStr;
 
+use aoc::config;
 use aoc::fs::get_file_contents;
-use aoc::math::two_sum;
-
-fn find_incorrect(numbers: &Vec<i64>, preamble: usize) -> Option<usize> {
-    (preamble..numbers.len())
-        .find(|&i| two_sum(&numbers[i-preamble..i].to_vec(), numbers[i]).is_none())
-}
+use aoc::runner::Runner;
+use day9::{crack_me, crack_me_prefix_sums, crack_xmas, crack_xmas2};
+use serde::Deserialize;
 
 
-fn crack_xmas(numbers: &Vec<i64>, preamble: usize) -> i64 {
-    match find_incorrect(numbers, preamble) {
-        None => panic!("Uncrackable!"),
-        Some(i) => numbers[i],
-    }
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Params {
+    preamble: usize,
 }
 
-fn find_min_max(numbers: &Vec<i64>, start: usize, end: usize, t: i64) -> Option<(i64, i64)> {
-    let mut _min = i64::MAX;
-    let mut _max = i64::MIN;
-    let mut sum = 0_i64;
-
-    for j in start..end {
-        sum += numbers[j];
-
-        match sum.cmp(&t) {
-            Ordering::Equal => { return Some((_min, _max)); },
-            Ordering::Greater => break,
-            Ordering::Less => {
-                _min = _min.min(numbers[j]);
-                _max = _max.max(numbers[j]);
-            },
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            preamble: 25,
         }
     }
-
-    None
 }
 
-fn crack_me(numbers: &Vec<i64>, index: usize) -> i64 {
-    let target_sum = numbers[index];
-
-    for i in 0..index {
-        if numbers[i] >= target_sum { continue; }
-
-        if let Some((_min, _max)) = find_min_max(numbers, i, index, target_sum) {
-            return _min + _max;
+impl config::Params for Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.preamble < 2 {
+            return Err("preamble must have at least 2 numbers".to_string());
         }
-    }
-
-    unreachable!();
-}
-
-fn crack_xmas2(numbers: &Vec<i64>, preamble: usize) -> i64 {
-    let i = match find_incorrect(numbers, preamble) {
-        None => panic!("Uncrackable!"),
-        Some(n) => n,
-    };
 
-    crack_me(numbers, i)
+        Ok(())
+    }
 }
 
 fn main() -> std::io::Result<()> {
+    aoc::embed_inputs!();
+    let mut runner = Runner::new(9);
+    let params: Params = runner.params();
     let lines = get_file_contents("data/input.txt")?;
     let numbers: Vec<i64> = lines
         .iter()
         .map(|line| i64::from_str(line).unwrap())
         .collect();
 
-    println!("Day 9 / Part 1: {}", crack_xmas(&numbers, 25));
-    println!("Day 9 / Part 2: {}", crack_xmas2(&numbers, 25));
+    runner.part(1, || crack_xmas(&numbers, params.preamble));
+    runner.variants(2)
+        .variant("naive", || crack_xmas2(&numbers, params.preamble, crack_me))
+        .variant("prefix-sums", || crack_xmas2(&numbers, params.preamble, crack_me_prefix_sums))
+        .run();
+    runner.finish();
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const NUMBERS: [i64; 20] = [
-        35,  20,  15,  25,  47,
-        40,  62,  55,  65,  95,
-        102, 117, 150, 182, 127,
-        219, 299, 277, 309, 576,
-    ];
-
-    #[test]
-    fn test_crack_xmas() {
-        assert_eq!(
-            127,
-            crack_xmas(&NUMBERS.to_vec(), 5),
-        );
-    }
-
-    #[test]
-    fn test_crack_xmas2() {
-        assert_eq!(
-            62,
-            crack_xmas2(&NUMBERS.to_vec(), 5),
-        );
-    }
-}
@@ -0,0 +1,169 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Allocation accounting for the solutions.
+//!
+//! `CountingAlloc` wraps the system allocator and keeps track of how many
+//! allocations were made, how many bytes were requested and the peak amount
+//! of live heap memory. It only becomes the global allocator when `aoc-core`
+//! is built with the `profile` feature, so regular runs pay nothing for it.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "profile")]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Whether the counting allocator is installed in this build.
+pub const ENABLED: bool = cfg!(feature = "profile");
+
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // A realloc is accounted as a fresh allocation of `new_size`
+            // bytes that replaces the old block.
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocation figures for a stretch of code.
+#[derive(Clone,Copy,Debug,Default,Eq,PartialEq)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+impl Stats {
+    /// Combines two consecutive measurements.
+    pub fn merge(&self, other: &Stats) -> Stats {
+        Stats {
+            allocations: self.allocations + other.allocations,
+            bytes: self.bytes + other.bytes,
+            peak: self.peak.max(other.peak),
+        }
+    }
+}
+
+/// Marks the beginning of a measurement.
+pub struct Probe {
+    allocations: usize,
+    bytes: usize,
+}
+
+impl Probe {
+    /// Starts measuring. The peak is reset to the memory that is currently
+    /// live, so `stop` reports the highest point reached in between.
+    pub fn start() -> Self {
+        PEAK.store(LIVE.load(Ordering::Relaxed), Ordering::Relaxed);
+
+        Probe {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn stop(self) -> Stats {
+        Stats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak: PEAK.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Runs `f` and returns its result along with the allocations it made.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Stats) {
+    let probe = Probe::start();
+    let res = f();
+
+    (res, probe.stop())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting_alloc() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let (_, stats) = measure(|| unsafe {
+            let ptr = CountingAlloc.alloc(layout);
+            let ptr = CountingAlloc.realloc(ptr, layout, 128);
+            CountingAlloc.dealloc(ptr, Layout::from_size_align(128, 8).unwrap());
+        });
+
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 192);
+        assert!(stats.peak >= 128);
+    }
+
+    #[test]
+    fn test_merge_stats() {
+        let a = Stats { allocations: 1, bytes: 10, peak: 10 };
+        let b = Stats { allocations: 2, bytes: 5, peak: 7 };
+
+        assert_eq!(Stats { allocations: 3, bytes: 15, peak: 10 }, a.merge(&b));
+    }
+}
@@ -0,0 +1,496 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Bit manipulation helpers.
+//!
+//! - `BitSet`: a growable set of small non-negative integers.
+//! - `Mask`: a fixed-width set of bits (up to 64).
+//! - `Pattern`: a fixed-width ternary pattern made of `0`, `1` and `X`
+//!   (floating) bits, like the masks from Day 14.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Not};
+use std::str::FromStr;
+
+const WORD: usize = 64;
+
+
+#[derive(Clone,Debug,Default,Eq,Hash,PartialEq)]
+pub struct BitSet {
+    // Trailing zero words are always trimmed, so that equal sets have
+    // equal representations.
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `i` to the set. Returns whether it was newly inserted.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (w, b) = (i / WORD, i % WORD);
+        if w >= self.words.len() {
+            self.words.resize(w + 1, 0);
+        }
+
+        let fresh = self.words[w] & (1 << b) == 0;
+        self.words[w] |= 1 << b;
+        fresh
+    }
+
+    /// Removes `i` from the set. Returns whether it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let present = self.contains(i);
+        if present {
+            self.words[i / WORD] &= !(1 << (i % WORD));
+            self.trim();
+        }
+        present
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        match self.words.get(i / WORD) {
+            None => false,
+            Some(w) => w & (1 << (i % WORD)) != 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.words.truncate(other.words.len());
+
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+        self.trim();
+    }
+
+    pub fn difference_with(&mut self, other: &BitSet) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= !b;
+        }
+        self.trim();
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let mut res = self.clone();
+        res.union_with(other);
+        res
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let mut res = self.clone();
+        res.intersect_with(other);
+        res
+    }
+
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        let mut res = self.clone();
+        res.difference_with(other);
+        res
+    }
+
+    pub fn is_subset(&self, other: &BitSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Iterates over the elements in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            (0..WORD).filter(move |b| word & (1 << b) != 0).map(move |b| w * WORD + b)
+        })
+    }
+
+    fn trim(&mut self) {
+        while let Some(0) = self.words.last() {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.insert(i);
+        }
+        set
+    }
+}
+
+fn width_mask(width: u32) -> u64 {
+    assert!(width as usize <= WORD, "Bit width {} is larger than {}", width, WORD);
+
+    if width as usize == WORD { u64::MAX } else { (1 << width) - 1 }
+}
+
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Mask {
+    bits: u64,
+    width: u32,
+}
+
+impl Mask {
+    /// An empty mask with room for `width` bits.
+    pub fn new(width: u32) -> Self {
+        Self::from_bits(0, width)
+    }
+
+    /// A mask with all of its `width` bits set.
+    pub fn full(width: u32) -> Self {
+        Self::from_bits(u64::MAX, width)
+    }
+
+    /// Builds a mask from `bits`, discarding anything past `width`.
+    pub fn from_bits(bits: u64, width: u32) -> Self {
+        Mask { bits: bits & width_mask(width), width }
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn set(&mut self, i: u32) {
+        assert!(i < self.width, "Bit {} out of range for width {}", i, self.width);
+        self.bits |= 1 << i;
+    }
+
+    pub fn clear(&mut self, i: u32) {
+        assert!(i < self.width, "Bit {} out of range for width {}", i, self.width);
+        self.bits &= !(1 << i);
+    }
+
+    pub fn get(&self, i: u32) -> bool {
+        i < self.width && self.bits & (1 << i) != 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.bits.count_ones()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.bits == width_mask(self.width)
+    }
+}
+
+impl BitOr for Mask {
+    type Output = Mask;
+
+    fn bitor(self, rhs: Mask) -> Mask {
+        Mask::from_bits(self.bits | rhs.bits, self.width.max(rhs.width))
+    }
+}
+
+impl BitAnd for Mask {
+    type Output = Mask;
+
+    fn bitand(self, rhs: Mask) -> Mask {
+        Mask::from_bits(self.bits & rhs.bits, self.width.max(rhs.width))
+    }
+}
+
+impl Not for Mask {
+    type Output = Mask;
+
+    fn not(self) -> Mask {
+        Mask::from_bits(!self.bits, self.width)
+    }
+}
+
+
+/// A ternary pattern over `width` bits. Each bit is either fixed to `0`,
+/// fixed to `1` or floating (`X`).
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Pattern {
+    ones: u64,
+    floating: u64,
+    width: u32,
+}
+
+impl Pattern {
+    /// A pattern where every bit is floating.
+    pub fn any(width: u32) -> Self {
+        Pattern { ones: 0, floating: width_mask(width), width }
+    }
+
+    /// A pattern that only matches `value`.
+    pub fn exact(value: u64, width: u32) -> Self {
+        Pattern { ones: value & width_mask(width), floating: 0, width }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn ones(&self) -> Mask {
+        Mask::from_bits(self.ones, self.width)
+    }
+
+    pub fn zeros(&self) -> Mask {
+        Mask::from_bits(!(self.ones | self.floating), self.width)
+    }
+
+    pub fn floating(&self) -> Mask {
+        Mask::from_bits(self.floating, self.width)
+    }
+
+    /// Number of values matched by the pattern.
+    pub fn count(&self) -> u128 {
+        1 << self.floating.count_ones()
+    }
+
+    /// Overwrites the fixed bits of `value`, leaving the floating ones
+    /// untouched.
+    pub fn apply(&self, value: u64) -> u64 {
+        (value & self.floating) | self.ones
+    }
+
+    /// Writes `value` through the pattern: `0` bits let the value through,
+    /// `1` bits force a one and `X` bits stay floating.
+    pub fn overlay(&self, value: u64) -> Pattern {
+        Pattern {
+            ones: (value & width_mask(self.width) & !self.floating) | self.ones,
+            floating: self.floating,
+            width: self.width,
+        }
+    }
+
+    pub fn matches(&self, value: u64) -> bool {
+        value & !width_mask(self.width) == 0 && value & !self.floating == self.ones
+    }
+
+    /// Patterns that have the same width and agree on every bit that is
+    /// fixed in both of them overlap; their intersection matches exactly
+    /// the values matched by both.
+    pub fn intersect(&self, other: &Pattern) -> Option<Pattern> {
+        if self.width != other.width { return None; }
+
+        let fixed = !self.floating & !other.floating;
+        if (self.ones ^ other.ones) & fixed != 0 {
+            return None;
+        }
+
+        Some(Pattern {
+            ones: self.ones | other.ones,
+            floating: self.floating & other.floating,
+            width: self.width,
+        })
+    }
+
+    /// Iterates, in increasing order, over every value matched by the
+    /// pattern.
+    pub fn expand(&self) -> Expand {
+        Expand { pattern: *self, next: Some(0) }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    /// Parses a pattern written with the most significant bit first.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > WORD {
+            return Err(format!("Pattern longer than {} bits: {}", WORD, s));
+        }
+
+        let mut pattern = Pattern::exact(0, s.len() as u32);
+        for (i, c) in s.chars().rev().enumerate() {
+            match c {
+                '0' => (),
+                '1' => pattern.ones |= 1 << i,
+                'X' => pattern.floating |= 1 << i,
+                _ => return Err(format!("Unknown pattern bit '{}' in {}", c, s)),
+            }
+        }
+
+        Ok(pattern)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s: String = (0..self.width)
+            .rev()
+            .map(|i| if self.floating & (1 << i) != 0 {
+                'X'
+            } else if self.ones & (1 << i) != 0 {
+                '1'
+            } else {
+                '0'
+            })
+            .collect();
+
+        write!(f, "{}", s)
+    }
+}
+
+pub struct Expand {
+    pattern: Pattern,
+    next: Option<u64>,
+}
+
+impl Iterator for Expand {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let sub = self.next?;
+        let floating = self.pattern.floating;
+
+        // Walks the subsets of the floating bits in increasing order.
+        self.next = if sub == floating {
+            None
+        } else {
+            Some(((sub | !floating).wrapping_add(1)) & floating)
+        };
+
+        Some(self.pattern.ones | sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset_insert_remove() {
+        let mut set = BitSet::new();
+
+        assert!(set.insert(3));
+        assert!(set.insert(200));
+        assert!(!set.insert(3));
+        assert_eq!(2, set.len());
+        assert!(set.contains(200));
+        assert!(!set.contains(199));
+
+        assert!(set.remove(200));
+        assert!(!set.remove(200));
+        assert_eq!(BitSet::from_iter(vec![3]), set);
+    }
+
+    #[test]
+    fn test_bitset_set_operations() {
+        let a = BitSet::from_iter(vec![1, 2, 3, 130]);
+        let b = BitSet::from_iter(vec![2, 3, 4]);
+
+        assert_eq!(vec![1, 2, 3, 4, 130], a.union(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![2, 3], a.intersection(&b).iter().collect::<Vec<_>>());
+        assert_eq!(vec![1, 130], a.difference(&b).iter().collect::<Vec<_>>());
+        assert!(BitSet::from_iter(vec![2, 3]).is_subset(&a));
+        assert!(!b.is_subset(&a));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut m = Mask::new(8);
+        m.set(0);
+        m.set(7);
+
+        assert_eq!(0x81, m.bits());
+        assert_eq!(2, m.count());
+        assert!(!m.is_full());
+        assert!((m | !m).is_full());
+        assert_eq!(Mask::new(8), m & !m);
+        assert_eq!(0x0F, Mask::from_bits(0xFF, 4).bits());
+        assert!(Mask::full(64).is_full());
+    }
+
+    #[test]
+    fn test_pattern_parse_and_display() {
+        let p = Pattern::from_str("X1001X").unwrap();
+
+        assert_eq!(6, p.width());
+        assert_eq!(0b010010, p.ones().bits());
+        assert_eq!(0b100001, p.floating().bits());
+        assert_eq!(0b001100, p.zeros().bits());
+        assert_eq!("X1001X", p.to_string());
+        assert!(Pattern::from_str("X2").is_err());
+    }
+
+    #[test]
+    fn test_pattern_apply() {
+        let p = Pattern::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
+
+        assert_eq!(73, p.apply(11));
+        assert_eq!(101, p.apply(101));
+        assert_eq!(64, p.apply(0));
+    }
+
+    #[test]
+    fn test_pattern_overlay_and_expand() {
+        let p = Pattern::from_str("000000000000000000000000000000X1001X").unwrap();
+        let addresses = p.overlay(42);
+
+        assert_eq!(4, addresses.count());
+        assert_eq!(vec![26, 27, 58, 59], addresses.expand().collect::<Vec<u64>>());
+        assert!(addresses.expand().all(|a| addresses.matches(a)));
+        assert!(!addresses.matches(42));
+    }
+
+    #[test]
+    fn test_pattern_intersect() {
+        let a = Pattern::from_str("1X0X").unwrap();
+        let b = Pattern::from_str("X10X").unwrap();
+        let c = Pattern::from_str("0XXX").unwrap();
+
+        assert_eq!(Some(Pattern::from_str("110X").unwrap()), a.intersect(&b));
+        assert_eq!(None, a.intersect(&c));
+        assert_eq!(vec![12, 13], a.intersect(&b).unwrap().expand().collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_pattern_full_width() {
+        let p = Pattern::any(64);
+
+        assert!(p.matches(u64::MAX));
+        assert_eq!(1 << 64, p.count());
+        assert_eq!(vec![7], Pattern::exact(7, 64).expand().collect::<Vec<u64>>());
+    }
+}
@@ -0,0 +1,213 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Cooperative limits for loops that might never end.
+//!
+//! A loop without a termination guarantee creates a `Budget` and calls
+//! `tick` on every iteration. Once the budget is exhausted, the loop gives up
+//! with a `DidNotConverge`, which describes whatever it had found so far, and
+//! the runner reports it instead of an answer.
+//!
+//! The limits come from the runner's `--max-steps` and `--timeout` flags. The
+//! step limit applies to each budget on its own, while the deadline is shared
+//! by every budget of the same part. Without them budgets are unlimited, so
+//! loops that can tell when they're stuck (e.g. a round that rules nothing
+//! out) also give up with `DidNotConverge::stalled`.
+
+use std::cell::Cell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+
+#[derive(Clone,Copy,Debug,Default,PartialEq)]
+pub struct Limits {
+    pub max_steps: Option<u64>,
+    pub timeout: Option<Duration>,
+}
+
+thread_local! {
+    static LIMITS: Cell<Limits> = const { Cell::new(Limits { max_steps: None, timeout: None }) };
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Sets the limits for the budgets created on the current thread from now
+/// on, and starts the clock.
+pub fn set_limits(limits: Limits) {
+    LIMITS.with(|l| l.set(limits));
+    start_clock();
+}
+
+/// Restarts the clock for the deadline, e.g. when a new part begins.
+pub fn start_clock() {
+    let timeout = LIMITS.with(|l| l.get().timeout);
+    DEADLINE.with(|d| d.set(timeout.map(|t| Instant::now() + t)));
+}
+
+// Looking at the clock on every step would slow down tight loops
+const CLOCK_EVERY: u64 = 256;
+
+#[derive(Clone,Debug,PartialEq)]
+pub enum Exhausted {
+    Steps(u64),
+    Deadline(Duration),
+}
+
+impl Exhausted {
+    pub fn with_state<S: Into<String>>(self, state: S) -> DidNotConverge {
+        DidNotConverge { reason: self.to_string(), state: state.into() }
+    }
+}
+
+impl fmt::Display for Exhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exhausted::Steps(n) => write!(f, "ran out of its {} step{}", n, if *n == 1 { "" } else { "s" }),
+            Exhausted::Deadline(t) => write!(f, "ran past its {:?} deadline", t),
+        }
+    }
+}
+
+/// A loop that gave up, along with what it had found so far.
+#[derive(Clone,Debug,PartialEq)]
+pub struct DidNotConverge {
+    pub reason: String,
+    pub state: String,
+}
+
+impl DidNotConverge {
+    /// A loop whose last step changed nothing, so no more steps will.
+    pub fn stalled<S: Into<String>>(state: S) -> Self {
+        DidNotConverge { reason: "stopped making progress".to_string(), state: state.into() }
+    }
+}
+
+impl fmt::Display for DidNotConverge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "did not converge ({})", self.reason)
+    }
+}
+
+#[derive(Clone,Debug)]
+pub struct Budget {
+    steps: u64,
+    max_steps: Option<u64>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl Budget {
+    /// A budget with the limits set for the current thread.
+    pub fn new() -> Self {
+        let limits = LIMITS.with(|l| l.get());
+        let deadline = DEADLINE.with(|d| d.get());
+
+        Budget {
+            steps: 0,
+            max_steps: limits.max_steps,
+            deadline: deadline.zip(limits.timeout),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Budget { steps: 0, max_steps: None, deadline: None }
+    }
+
+    pub fn with_max_steps(max_steps: u64) -> Self {
+        Budget { max_steps: Some(max_steps), ..Budget::unlimited() }
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Budget { deadline: Some((Instant::now() + timeout, timeout)), ..Budget::unlimited() }
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Accounts for one more step, failing if it goes over the budget.
+    pub fn tick(&mut self) -> Result<(), Exhausted> {
+        if let Some(max) = self.max_steps {
+            if self.steps >= max {
+                return Err(Exhausted::Steps(max));
+            }
+        }
+
+        if let Some((deadline, timeout)) = self.deadline {
+            if self.steps.is_multiple_of(CLOCK_EVERY) && Instant::now() >= deadline {
+                return Err(Exhausted::Deadline(timeout));
+            }
+        }
+
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_steps() {
+        let mut budget = Budget::with_max_steps(3);
+
+        assert_eq!(Ok(()), budget.tick());
+        assert_eq!(Ok(()), budget.tick());
+        assert_eq!(Ok(()), budget.tick());
+        assert_eq!(Err(Exhausted::Steps(3)), budget.tick());
+        assert_eq!(3, budget.steps());
+    }
+
+    #[test]
+    fn test_deadline() {
+        let mut budget = Budget::with_timeout(Duration::from_millis(0));
+
+        assert_eq!(Err(Exhausted::Deadline(Duration::from_millis(0))), budget.tick());
+
+        let mut budget = Budget::unlimited();
+        assert!((0..1_000_000).all(|_| budget.tick().is_ok()));
+        assert_eq!(1_000_000, budget.steps());
+    }
+
+    #[test]
+    fn test_thread_limits() {
+        assert_eq!(None, Budget::new().max_steps);
+
+        set_limits(Limits { max_steps: Some(1), timeout: Some(Duration::from_secs(60)) });
+        let mut budget = Budget::new();
+
+        assert_eq!(Ok(()), budget.tick());
+        assert_eq!(
+            "did not converge (ran out of its 1 step)",
+            budget.tick().unwrap_err().with_state("stuck").to_string(),
+        );
+        assert_eq!(
+            "did not converge (stopped making progress)",
+            DidNotConverge::stalled("stuck").to_string(),
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Helpers for the days' build scripts.
+//!
+//! A day embeds its inputs with a `build.rs` that calls `embed_inputs`, and
+//! by calling `aoc::embed_inputs!()` at the start of `main`. When built with
+//! `--features embed`, every file in its `data` folder is baked into the
+//! binary, and `aoc::fs::get_file_contents` reads it from there instead of
+//! the current directory.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+
+// Files in `dir`, relative to `root` and sorted, so the output is stable.
+fn data_files(root: &Path, dir: &Path, acc: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            data_files(root, &path, acc);
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            acc.push(rel.to_path_buf());
+        }
+    }
+
+    acc.sort();
+}
+
+// The Rust expression with the table of embedded files, which is empty
+// unless embedding.
+fn embedded_table(root: &Path, embed: bool) -> (String, Vec<PathBuf>) {
+    let mut files = Vec::new();
+    if embed {
+        data_files(root, &root.join("data"), &mut files);
+    }
+
+    let entries: Vec<String> = files
+        .iter()
+        .map(|rel| {
+            let name: Vec<String> = rel.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            format!(
+                "    ({:?}, include_str!({:?})),\n",
+                name.join("/"), root.join(rel).to_string_lossy(),
+            )
+        })
+        .collect();
+
+    (format!("&[\n{}]\n", entries.concat()), files)
+}
+
+/// Writes the table of embedded inputs for `aoc::embed_inputs!()`. Meant to
+/// be called from a build script.
+pub fn embed_inputs() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    let embed = env::var_os("CARGO_FEATURE_EMBED").is_some();
+
+    let (table, files) = embedded_table(&root, embed);
+    fs::write(&out, table).unwrap();
+
+    println!("cargo:rerun-if-changed=data");
+    for rel in files {
+        println!("cargo:rerun-if-changed={}", rel.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_embedded_table() {
+        let root = env::temp_dir().join(format!("aoc-build-test-{}", process::id()));
+        fs::create_dir_all(root.join("data/examples")).unwrap();
+        fs::write(root.join("data/input.txt"), "1\n2\n").unwrap();
+        fs::write(root.join("data/examples/small.txt"), "1\n").unwrap();
+
+        assert_eq!("&[\n]\n", embedded_table(&root, false).0);
+
+        let (table, files) = embedded_table(&root, true);
+        assert_eq!(
+            vec![PathBuf::from("data/examples/small.txt"), PathBuf::from("data/input.txt")],
+            files,
+        );
+        assert!(table.contains("(\"data/examples/small.txt\", include_str!("));
+        assert!(table.contains("(\"data/input.txt\", include_str!("));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
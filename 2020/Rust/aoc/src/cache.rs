@@ -0,0 +1,194 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! On-disk cache of parsed inputs and answers.
+//!
+//! An answer is stored under a key made of the day, the part, a fingerprint
+//! of every input read through `aoc::fs`, the puzzle parameters and the
+//! version of the solver. The version is taken from the running executable,
+//! so rebuilding a day invalidates whatever it had cached.
+//!
+//! Parsed inputs are stored as JSON under the same fingerprint and version,
+//! along with a name telling apart the inputs of a day. They don't depend on
+//! the puzzle parameters.
+
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+
+thread_local! {
+    static INPUTS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Accounts for input read by the solver. Called by `aoc::fs`.
+pub fn note_input(bytes: &[u8]) {
+    INPUTS.with(|inputs| {
+        let mut hasher = DefaultHasher::new();
+        inputs.get().hash(&mut hasher);
+        bytes.hash(&mut hasher);
+        inputs.set(hasher.finish());
+    });
+}
+
+/// Fingerprint of all the input read so far on the current thread.
+pub fn inputs() -> u64 {
+    INPUTS.with(|inputs| inputs.get())
+}
+
+/// Fingerprint of the running executable, which changes whenever it's
+/// rebuilt.
+pub fn solver_version() -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    if let Ok(meta) = env::current_exe().and_then(fs::metadata) {
+        meta.len().hash(&mut hasher);
+        meta.modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Key {
+    pub day: u8,
+    pub part: u8,
+    pub inputs: u64,
+    pub params: u64,
+    pub version: u64,
+}
+
+impl Key {
+    fn file_name(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+
+        format!("day{}-part{}-{:016x}", self.day, self.part, hasher.finish())
+    }
+}
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct InputKey<'a> {
+    pub day: u8,
+    pub name: &'a str,
+    pub inputs: u64,
+    pub version: u64,
+}
+
+impl InputKey<'_> {
+    fn file_name(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+
+        format!("day{}-{}-{:016x}.json", self.day, self.name, hasher.finish())
+    }
+}
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Cache { dir: dir.as_ref().to_path_buf() }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<String> {
+        fs::read_to_string(self.dir.join(key.file_name())).ok()
+    }
+
+    pub fn put(&self, key: &Key, answer: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(key.file_name()), answer)
+    }
+
+    /// Loads a parsed input, or `None` when it isn't stored or no longer
+    /// deserializes into `T`.
+    pub fn get_parsed<T: DeserializeOwned>(&self, key: &InputKey) -> Option<T> {
+        let json = fs::read_to_string(self.dir.join(key.file_name())).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn put_parsed<T: Serialize>(&self, key: &InputKey, parsed: &T) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(key.file_name()), serde_json::to_string(parsed)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_inputs() {
+        let before = inputs();
+        note_input(b"1721\n979\n");
+        let once = inputs();
+        note_input(b"1721\n979\n");
+
+        assert_ne!(before, once);
+        assert_ne!(once, inputs());
+    }
+
+    #[test]
+    fn test_get_put() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", process::id()));
+        let cache = Cache::new(&dir);
+        let key = Key { day: 15, part: 2, inputs: 1, params: 2, version: 3 };
+
+        assert_eq!(None, cache.get(&key));
+        cache.put(&key, "62714").unwrap();
+        assert_eq!(Some("62714".to_string()), cache.get(&key));
+        assert_eq!(None, cache.get(&Key { part: 1, ..key }));
+        assert_eq!(None, cache.get(&Key { version: 4, ..key }));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_put_parsed() {
+        let dir = env::temp_dir().join(format!("aoc-cache-parsed-test-{}", process::id()));
+        let cache = Cache::new(&dir);
+        let key = InputKey { day: 7, name: "rules", inputs: 1, version: 2 };
+        let rules = vec![("shiny gold".to_string(), vec![(1, 2), (2, 3)])];
+
+        assert_eq!(None, cache.get_parsed::<Vec<(String, Vec<(usize, usize)>)>>(&key));
+        cache.put_parsed(&key, &rules).unwrap();
+        assert_eq!(Some(rules), cache.get_parsed(&key));
+        assert_eq!(None, cache.get_parsed::<Vec<u8>>(&key));
+        assert_eq!(None, cache.get_parsed::<Vec<u8>>(&InputKey { name: "bags", ..key }));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
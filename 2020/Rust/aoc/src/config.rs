@@ -0,0 +1,299 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Puzzle parameters.
+//!
+//! A day describes its parameters as a struct whose `Default` holds the
+//! values from the puzzle statement. Any of them can be changed in an
+//! `aoc.toml` file next to the day's `Cargo.toml`, or on the command line with
+//! `--set key=value`, which wins over the file. Everything is checked while
+//! loading, before any part is solved.
+//!
+//! The struct needs `#[serde(default, deny_unknown_fields)]`, so that missing
+//! keys keep their default and misspelled ones are reported.
+//!
+//! Parameters going through TOML can't use enum variants with data, so those
+//! are written as maps with a single entry, e.g. `{ regex = "^a" }`, and read
+//! with `single_entry`.
+
+use std::fmt;
+
+use regex::Regex;
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, Error, MapAccess};
+use toml::value::{Table, Value};
+
+
+pub trait Params: DeserializeOwned + Default {
+    /// Checks the values that are well typed but still make no sense.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// Values are written in TOML, but bare words are taken as strings, so that
+// `--set color=shiny gold` works without quoting.
+fn parse_value(s: &str) -> Value {
+    format!("value = {}", s)
+        .parse::<Value>()
+        .ok()
+        .and_then(|t| t.get("value").cloned())
+        .unwrap_or_else(|| Value::String(s.to_string()))
+}
+
+/// Builds the parameters out of the contents of a configuration file and the
+/// overrides from the command line.
+pub fn load<P: Params>(file: Option<&str>, overrides: &[(String, String)]) -> Result<P, String> {
+    let mut table = match file {
+        None => Table::new(),
+        Some(contents) => contents.parse::<Value>()
+            .map_err(|e| e.to_string())?
+            .try_into::<Table>()
+            .map_err(|e| e.to_string())?,
+    };
+
+    for (key, value) in overrides {
+        table.insert(key.to_string(), parse_value(value));
+    }
+
+    let params: P = Value::Table(table).try_into().map_err(|e| e.to_string())?;
+    params.validate()?;
+
+    Ok(params)
+}
+
+/// A regex, compiled when the parameters are loaded. Patterns are equal when
+/// they were written the same and match the same way.
+#[derive(Clone,Debug)]
+pub struct TextPattern {
+    source: String,
+    regex: Regex,
+}
+
+impl TextPattern {
+    /// A pattern that may match anywhere in the text.
+    pub fn new(source: &str) -> Result<Self, String> {
+        TextPattern::compile(source, source)
+    }
+
+    /// A pattern that must match the whole text.
+    pub fn whole(source: &str) -> Result<Self, String> {
+        TextPattern::compile(source, &format!("^(?:{})$", source))
+    }
+
+    fn compile(source: &str, regex: &str) -> Result<Self, String> {
+        match Regex::new(regex) {
+            Ok(regex) => Ok(TextPattern { source: source.to_string(), regex }),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for TextPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+    }
+}
+
+impl fmt::Display for TextPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Deserializes into a pattern that may match anywhere, see `TextPattern::new`.
+impl<'de> Deserialize<'de> for TextPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        TextPattern::new(&s).map_err(D::Error::custom)
+    }
+}
+
+/// Reads a map with a single entry, like `{ range = [1920, 2002] }`. `entry`
+/// is handed the key and reads the value from the map. `what` names the map
+/// in the errors.
+pub fn single_entry<'de, A, T, F>(mut map: A, what: &str, entry: F) -> Result<T, A::Error>
+where
+    A: MapAccess<'de>,
+    F: FnOnce(&str, &mut A) -> Result<T, A::Error>,
+{
+    let key: String = map.next_key()?.ok_or_else(|| A::Error::custom(format!("empty {}", what)))?;
+    let value = entry(&key, &mut map)?;
+
+    match map.next_key::<String>()? {
+        Some(other) => Err(A::Error::custom(format!("{} and {} in the same {}", key, other, what))),
+        None => Ok(value),
+    }
+}
+
+/// Splits a `key=value` override.
+pub fn parse_override(s: &str) -> Result<(String, String), String> {
+    match s.find('=') {
+        Some(i) if i > 0 => Ok((s[..i].trim().to_string(), s[i+1..].trim().to_string())),
+        _ => Err(format!("Expected key=value, found: {}", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(default, deny_unknown_fields)]
+    struct Sample {
+        preamble: usize,
+        color: String,
+        slopes: Vec<(usize, usize)>,
+    }
+
+    impl Default for Sample {
+        fn default() -> Self {
+            Sample { preamble: 25, color: "shiny gold".to_string(), slopes: vec![(3, 1)] }
+        }
+    }
+
+    impl Params for Sample {
+        fn validate(&self) -> Result<(), String> {
+            if self.preamble < 2 {
+                return Err("preamble must be at least 2".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    fn set(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        assert_eq!(Ok(Sample::default()), load::<Sample>(None, &[]));
+
+        let file = "preamble = 5\nslopes = [[1, 1], [1, 2]]\n";
+        assert_eq!(
+            Ok(Sample { preamble: 5, color: "dark red".to_string(), slopes: vec![(1, 1), (1, 2)] }),
+            load::<Sample>(Some(file), &[set("color", "dark red")]),
+        );
+        assert_eq!(
+            Ok(Sample { preamble: 7, ..Sample::default() }),
+            load::<Sample>(Some(file), &[set("preamble", "7"), set("slopes", "[[3, 1]]")]),
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(load::<Sample>(Some("preambel = 5"), &[]).is_err());
+        assert!(load::<Sample>(Some("preamble = \"five\""), &[]).is_err());
+        assert!(load::<Sample>(None, &[set("preamble", "-1")]).is_err());
+        assert!(load::<Sample>(Some("preamble = "), &[]).is_err());
+        assert_eq!(
+            Err("preamble must be at least 2".to_string()),
+            load::<Sample>(None, &[set("preamble", "1")]),
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let anywhere = TextPattern::new("[0-9]{9}").unwrap();
+        let whole = TextPattern::whole("[0-9]{9}").unwrap();
+
+        assert!(anywhere.is_match("0123456789"));
+        assert!(!whole.is_match("0123456789"));
+        assert!(whole.is_match("012345678"));
+        assert_eq!("[0-9]{9}", whole.as_str());
+        assert_ne!(anywhere, whole);
+        assert_eq!(anywhere, TextPattern::new("[0-9]{9}").unwrap());
+        assert!(TextPattern::new("[0-9").is_err());
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(default, deny_unknown_fields)]
+    struct Rules {
+        rule: Option<Rule>,
+    }
+
+    impl Params for Rules {}
+
+    #[derive(Debug, PartialEq)]
+    enum Rule {
+        Regex(TextPattern),
+        Forbidden(String),
+    }
+
+    struct RuleVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for RuleVisitor {
+        type Value = Rule;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a rule")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Rule, A::Error> {
+            single_entry(map, "rule", |key, map| match key {
+                "regex" => Ok(Rule::Regex(map.next_value()?)),
+                "forbidden" => Ok(Rule::Forbidden(map.next_value()?)),
+                _ => Err(A::Error::unknown_variant(key, &["regex", "forbidden"])),
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rule {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(RuleVisitor)
+        }
+    }
+
+    #[test]
+    fn test_single_entry() {
+        assert_eq!(
+            Ok(Rules { rule: Some(Rule::Regex(TextPattern::new("^a").unwrap())) }),
+            load::<Rules>(Some("rule = { regex = \"^a\" }"), &[]),
+        );
+        assert_eq!(
+            Ok(Rules { rule: Some(Rule::Forbidden("iol".to_string())) }),
+            load::<Rules>(None, &[set("rule", "{ forbidden = \"iol\" }")]),
+        );
+
+        let error = |toml| load::<Rules>(Some(toml), &[]).unwrap_err();
+        assert!(error("rule = {}").contains("empty rule"));
+        assert!(error("rule = { regex = \"a\", forbidden = \"b\" }").contains("in the same rule"));
+        assert!(error("rule = { regex = \"[a\" }").contains("unclosed"));
+        assert!(error("rule = { count = 1 }").contains("unknown variant"));
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(Ok(set("color", "shiny gold")), parse_override("color = shiny gold"));
+        assert!(parse_override("color").is_err());
+        assert!(parse_override("=5").is_err());
+    }
+}
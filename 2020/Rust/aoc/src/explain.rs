@@ -0,0 +1,113 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Narratives of how a solution reached its answer.
+//!
+//! Solvers describe the interesting steps they take with the `explain!`
+//! macro. Records are only kept when explain mode is enabled (the runner's
+//! `--explain` flag), otherwise the macro doesn't even format its message.
+
+use std::cell::RefCell;
+use std::fmt;
+
+
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Record {
+    pub topic: String,
+    pub message: String,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.topic, self.message)
+    }
+}
+
+thread_local! {
+    static NARRATIVE: RefCell<Option<Vec<Record>>> = const { RefCell::new(None) };
+}
+
+/// Starts keeping records on the current thread.
+pub fn enable() {
+    NARRATIVE.with(|n| {
+        let mut n = n.borrow_mut();
+        if n.is_none() {
+            *n = Some(Vec::new());
+        }
+    });
+}
+
+pub fn enabled() -> bool {
+    NARRATIVE.with(|n| n.borrow().is_some())
+}
+
+pub fn record(topic: &str, message: String) {
+    NARRATIVE.with(|n| {
+        if let Some(records) = n.borrow_mut().as_mut() {
+            records.push(Record { topic: topic.to_string(), message });
+        }
+    });
+}
+
+/// Returns the records kept so far and starts afresh.
+pub fn take() -> Vec<Record> {
+    NARRATIVE.with(|n| match n.borrow_mut().as_mut() {
+        None => Vec::new(),
+        Some(records) => std::mem::take(records),
+    })
+}
+
+/// Records a step of the narrative, e.g.
+/// `explain!("fix", "flipped instruction {}", i)`.
+#[macro_export]
+macro_rules! explain {
+    ($topic:expr, $($arg:tt)*) => {
+        if $crate::explain::enabled() {
+            $crate::explain::record($topic, format!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_by_default() {
+        explain!("test", "{}", 1);
+
+        assert!(!enabled());
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn test_records() {
+        enable();
+        explain!("fix", "flipped instruction {}", 7);
+        explain!("fix", "done");
+
+        let records = take();
+        assert_eq!(2, records.len());
+        assert_eq!("[fix] flipped instruction 7", records[0].to_string());
+        assert!(take().is_empty());
+    }
+}
@@ -21,14 +21,87 @@
 // SOFTWARE.
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read};
+use std::sync::OnceLock;
 
+use crate::cache;
+
+
+// Files baked into the binary, by their path relative to the day's folder
+type Embedded = &'static [(&'static str, &'static str)];
+
+static EMBEDDED: OnceLock<Embedded> = OnceLock::new();
+
+/// Makes `get_file_contents` read these files from memory. Days do it through
+/// `aoc::embed_inputs!()`.
+pub fn embed(files: Embedded) {
+    if EMBEDDED.set(files).is_err() {
+        panic!("Inputs were already embedded");
+    }
+}
+
+/// Registers the inputs embedded by the day's build script (see
+/// `aoc::build`). Without the `embed` feature there are none, and inputs are
+/// read from the current directory.
+#[macro_export]
+macro_rules! embed_inputs {
+    () => {
+        $crate::fs::embed(include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs")))
+    };
+}
+
+fn embedded(name: &str) -> Option<&'static str> {
+    let name = name.trim_start_matches("./");
+
+    EMBEDDED
+        .get()?
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, contents)| *contents)
+}
+
+fn read(name: &str) -> io::Result<String> {
+    if let Some(contents) = embedded(name) {
+        return Ok(contents.to_string());
+    }
 
-pub fn get_file_contents(name: &str) -> std::io::Result<Vec<String>> {
     let mut buffer = String::new();
-    let mut file = File::open(name)?;
+    let mut file = File::open(name).map_err(|e| io::Error::new(
+        e.kind(),
+        format!(
+            "Couldn't open {}: {}. Run from the day's folder, or build with --features embed",
+            name, e,
+        ),
+    ))?;
+
+    file.read_to_string(&mut buffer)?;
+    Ok(buffer)
+}
+
+pub fn get_file_contents(name: &str) -> std::io::Result<Vec<String>> {
+    let buffer = read(name)?;
+    cache::note_input(buffer.as_bytes());
+
+    Ok(lines(&buffer))
+}
+
+/// The lines of an input, as `get_file_contents` returns them.
+pub fn lines(buffer: &str) -> Vec<String> {
+    buffer.trim().split('\n').map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_inputs() {
+        embed(&[("data/input.txt", "1\n2\n3\n")]);
+
+        assert_eq!(vec!["1", "2", "3"], get_file_contents("data/input.txt").unwrap());
+        assert_eq!(vec!["1", "2", "3"], get_file_contents("./data/input.txt").unwrap());
 
-    file.read_to_string(&mut buffer).unwrap();
-    
-    Ok(buffer.trim().split("\n").map(|x| String::from(x)).collect())
+        let e = get_file_contents("data/missing.txt").unwrap_err();
+        assert!(e.to_string().starts_with("Couldn't open data/missing.txt: "));
+    }
 }
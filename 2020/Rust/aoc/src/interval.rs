@@ -0,0 +1,199 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Sets of integers stored as ranges.
+//!
+//! `IntervalSet` keeps its ranges sorted, disjoint and non-adjacent, so
+//! that equal sets have equal representations, membership is a binary
+//! search, and the set operations are a single merge of both lists.
+
+use std::cmp::{max, min};
+use std::iter::{self, FromIterator};
+
+use num::PrimInt;
+
+
+#[derive(Clone,Debug,Default,Eq,Hash,PartialEq)]
+pub struct IntervalSet<T> {
+    // Inclusive ranges
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Adds every integer in `start..=end`, merging it with the ranges it
+    /// overlaps or touches. Empty ranges are ignored.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        // Ranges strictly before (and not touching) the new one, and the ones
+        // up to the last that overlaps or touches it
+        let lo = self.ranges
+            .partition_point(|&(_, e)| e.checked_add(&T::one()).is_some_and(|e| e < start));
+        let hi = self.ranges
+            .partition_point(|&(s, _)| end.checked_add(&T::one()).is_none_or(|end| s <= end));
+
+        let merged = if lo < hi {
+            (min(start, self.ranges[lo].0), max(end, self.ranges[hi - 1].1))
+        } else {
+            (start, end)
+        };
+
+        self.ranges.splice(lo..hi, iter::once(merged));
+    }
+
+    /// Whether `x` is in the set, in O(log n).
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.ranges.partition_point(|&(s, _)| s <= x);
+        i > 0 && x <= self.ranges[i - 1].1
+    }
+
+    /// The ranges in the set, in increasing order.
+    pub fn ranges(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many integers are in the set, or `None` if there are too many for
+    /// a `T`, as with `0..=usize::MAX`.
+    pub fn covered_len(&self) -> Option<T> {
+        self.ranges
+            .iter()
+            .try_fold(T::zero(), |acc, &(s, e)| e.checked_sub(&s)?.checked_add(&T::one())?.checked_add(&acc))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut res = self.clone();
+        other.ranges().for_each(|(s, e)| res.insert(s, e));
+        res
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+
+        while i < a.len() && j < b.len() {
+            let (s, e) = (max(a[i].0, b[j].0), min(a[i].1, b[j].1));
+            if s <= e {
+                ranges.push((s, e));
+            }
+
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let b = &other.ranges;
+        let mut j = 0;
+        let mut ranges = Vec::new();
+
+        for &(s, e) in self.ranges.iter() {
+            while j < b.len() && b[j].1 < s {
+                j += 1;
+            }
+
+            // What's left of `s..=e` starts at `rest`, until a range from
+            // `other` covers its end
+            let mut rest = Some(s);
+            let mut k = j;
+            while let Some(r) = rest {
+                if k >= b.len() || b[k].0 > e {
+                    ranges.push((r, e));
+                    break;
+                }
+
+                if b[k].0 > r {
+                    ranges.push((r, b[k].0 - T::one()));
+                }
+                rest = if b[k].1 < e { Some(b[k].1 + T::one()) } else { None };
+                k += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: PrimInt> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|(s, e)| set.insert(s, e));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(i32, i32)]) -> IntervalSet<i32> {
+        ranges.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let s = set(&[(10, 12), (1, 3), (5, 7), (4, 4), (20, 19), (11, 15)]);
+        assert_eq!(vec![(1, 7), (10, 15)], s.ranges().collect::<Vec<_>>());
+        assert_eq!(Some(13), s.covered_len());
+
+        assert!(s.contains(1) && s.contains(7) && s.contains(10) && s.contains(15));
+        assert!(!s.contains(0) && !s.contains(8) && !s.contains(16));
+        assert!(set(&[]).is_empty());
+
+        let s: IntervalSet<u8> = vec![(250, 255), (0, 3), (4, 249)].into_iter().collect();
+        assert_eq!(vec![(0, 255)], s.ranges().collect::<Vec<_>>());
+        assert_eq!(None, s.covered_len());
+        assert_eq!(Some(255), IntervalSet::<u8>::from_iter(vec![(0, 254)]).covered_len());
+        assert_eq!(None, IntervalSet::<i8>::from_iter(vec![(-128, 0)]).covered_len());
+        assert_eq!(Some(127), IntervalSet::<i8>::from_iter(vec![(-127, -1)]).covered_len());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 8), (10, 22), (30, 40)]);
+
+        assert_eq!(set(&[(1, 40)]), a.union(&b));
+        assert_eq!(set(&[(5, 8), (10, 10), (20, 22), (30, 30)]), a.intersection(&b));
+        assert_eq!(set(&[(1, 4), (9, 9), (23, 29)]), a.difference(&b));
+        assert_eq!(set(&[(11, 19), (31, 40)]), b.difference(&a));
+
+        assert_eq!(set(&[]), a.difference(&a));
+        assert_eq!(a, a.difference(&set(&[])));
+        assert_eq!(set(&[]), a.intersection(&set(&[])));
+    }
+}
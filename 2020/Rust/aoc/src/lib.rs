@@ -20,5 +20,19 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub mod alloc;
+pub mod bits;
+pub mod budget;
+pub mod build;
+pub mod cache;
+pub mod config;
+#[macro_use]
+pub mod explain;
 pub mod fs;
+pub mod interval;
 pub mod math;
+pub mod numeric;
+pub mod plugin;
+pub mod ring;
+pub mod runner;
+pub mod sim;
@@ -25,7 +25,7 @@ use std::hash::Hash;
 
 use num::Integer;
 
-pub fn two_sum<T: Integer + Copy + Hash>(nums: &Vec<T>, target: T) -> Option<(T, T)> {
+pub fn two_sum<T: Integer + Copy + Hash>(nums: &[T], target: T) -> Option<(T, T)> {
     let mut numbers = HashSet::new();
 
     for i in nums.iter() {
@@ -46,7 +46,7 @@ mod tests {
     fn test_two_sum_is_some() {
         assert_eq!(
             Some((25, 15)),
-            two_sum(&vec![35, 20, 15, 25, 47], 40),
+            two_sum(&[35, 20, 15, 25, 47], 40),
         );
     }
 
@@ -54,7 +54,7 @@ mod tests {
     fn test_two_sum_is_none() {
         assert_eq!(
             None,
-            two_sum(&vec![1, 2, 3, 4, 5], 10),
+            two_sum(&[1, 2, 3, 4, 5], 10),
         );
     }
 }
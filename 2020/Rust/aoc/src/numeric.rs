@@ -0,0 +1,106 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Integers for solvers whose answers may outgrow machine integers.
+//!
+//! Such solvers are generic over `Number`, and their days solve with
+//! `Wide<T>`: the machine integer `T` by default, or an arbitrary-precision
+//! integer of the same signedness when aoc-core is built with the `bigint`
+//! feature (e.g. `cargo run --release --features bigint`).
+
+use std::fmt;
+
+use num::FromPrimitive;
+#[cfg(feature = "bigint")]
+use num_bigint::{BigInt, BigUint};
+
+// What generic solvers need besides `Number`
+pub use num::{Integer, Signed};
+
+
+pub trait Number: Integer + Clone + FromPrimitive + fmt::Debug + fmt::Display {
+    /// Parses a decimal number.
+    fn parse(s: &str) -> Option<Self> {
+        Self::from_str_radix(s, 10).ok()
+    }
+
+    /// Converts a count, e.g. how many bags fit in another one.
+    fn from_count(n: usize) -> Self {
+        Self::from_usize(n).unwrap_or_else(|| panic!("{} doesn't fit", n))
+    }
+}
+
+impl<T> Number for T
+where
+    T: Integer + Clone + FromPrimitive + fmt::Debug + fmt::Display
+{}
+
+/// Picks the integer a day solves with, see `Wide`.
+pub trait Widen {
+    type Output: Number;
+}
+
+/// `T`, or an arbitrary-precision integer with the `bigint` feature.
+pub type Wide<T> = <T as Widen>::Output;
+
+macro_rules! widen {
+    ($big:ty => $($t:ty),*) => {
+        $(
+            impl Widen for $t {
+                #[cfg(not(feature = "bigint"))]
+                type Output = $t;
+                #[cfg(feature = "bigint")]
+                type Output = $big;
+            }
+        )*
+    };
+}
+
+widen!(BigUint => u32, u64, u128, usize);
+widen!(BigInt => i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum<N: Number>(numbers: &[&str]) -> N {
+        numbers.iter().fold(N::zero(), |acc, n| acc + N::parse(n).unwrap())
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(6, sum::<i64>(&["1", "2", "3"]));
+        assert_eq!(Some(42_u8), u8::parse("42"));
+        assert_eq!(None, u8::parse("256"));
+        assert_eq!(7_i128, i128::from_count(7));
+
+        let wide: Wide<u64> = sum(&["18446744073709551615", "0"]);
+        assert_eq!("18446744073709551615", wide.to_string());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() {
+        let wide: Wide<u64> = sum(&["18446744073709551615", "1"]);
+        assert_eq!("18446744073709551616", wide.to_string());
+    }
+}
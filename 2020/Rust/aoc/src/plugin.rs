@@ -0,0 +1,215 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Stable C ABI for days built as plugins.
+//!
+//! A day whose library is built as a `cdylib` exports these functions through
+//! `aoc::plugin!`, so that `aoc run --plugins DIR` can load it at runtime:
+//!
+//! - `aoc_plugin_info() -> Info`, the puzzle it solves, and whether it reads
+//!   an input at all. Some days have their input in the code;
+//! - `aoc_plugin_solve(part, input, len, answer) -> i32`, which solves a part
+//!   for the `len` bytes of UTF-8 input. On `OK`, `*answer` is the answer as a
+//!   NUL-terminated string, and on `FAILED` it's the error. On `NO_SUCH_PART`
+//!   it's null;
+//! - `aoc_plugin_free(answer)`, which releases a string handed out by the
+//!   plugin.
+//!
+//! Hosts must check `Info::abi_version` against `ABI_VERSION` before calling
+//! anything else.
+
+use std::any::Any;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::str;
+
+use crate::fs;
+
+
+pub const ABI_VERSION: u32 = 2;
+pub const YEAR: u16 = 2020;
+
+pub const INFO_SYMBOL: &[u8] = b"aoc_plugin_info";
+pub const SOLVE_SYMBOL: &[u8] = b"aoc_plugin_solve";
+pub const FREE_SYMBOL: &[u8] = b"aoc_plugin_free";
+
+pub const OK: i32 = 0;
+pub const FAILED: i32 = 1;
+pub const NO_SUCH_PART: i32 = 2;
+
+#[repr(C)]
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct Info {
+    pub abi_version: u32,
+    pub year: u16,
+    pub day: u8,
+    pub parts: u8,
+    pub reads_input: bool,
+}
+
+impl Info {
+    pub fn new(day: u8, parts: u8) -> Self {
+        Info { abi_version: ABI_VERSION, year: YEAR, day, parts, reads_input: true }
+    }
+}
+
+pub type InfoFn = extern "C" fn() -> Info;
+pub type SolveFn = unsafe extern "C" fn(u8, *const u8, usize, *mut *mut c_char) -> i32;
+pub type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// Solves a part for the input lines, or returns `None` if there's no such
+/// part.
+pub type Solver = fn(u8, &[String]) -> Option<String>;
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "the solver panicked".to_string(),
+        },
+    }
+}
+
+fn to_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', "")).unwrap().into_raw()
+}
+
+/// Body of `aoc_plugin_solve`. Panics are reported as failures, since they
+/// can't unwind into the host.
+///
+/// # Safety
+///
+/// `input` must point at `len` readable bytes, and `answer` must be writable.
+pub unsafe fn solve(
+    solver: Solver,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    answer: *mut *mut c_char,
+) -> i32 {
+    let input = if len == 0 { &[] } else { slice::from_raw_parts(input, len) };
+    let outcome = match str::from_utf8(input) {
+        Ok(text) => {
+            let lines = fs::lines(text);
+            panic::catch_unwind(|| solver(part, &lines)).map_err(panic_message)
+        },
+        Err(e) => Err(format!("The input isn't UTF-8: {}", e)),
+    };
+
+    let (status, text) = match outcome {
+        Ok(Some(a)) => (OK, to_c_string(a)),
+        Ok(None) => (NO_SUCH_PART, ptr::null_mut()),
+        Err(e) => (FAILED, to_c_string(e)),
+    };
+
+    *answer = text;
+    status
+}
+
+/// Body of `aoc_plugin_free`.
+///
+/// # Safety
+///
+/// `answer` must be null or a string handed out by `solve`, not yet freed.
+pub unsafe fn free(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
+
+/// Exports the plugin entry points of a day, e.g.
+/// `aoc::plugin!(day: 5, parts: 2, solve: solve);`. Days with their input in
+/// the code add `reads_input: false`, and are handed no input.
+#[macro_export]
+macro_rules! plugin {
+    (day: $day:expr, parts: $parts:expr, solve: $solve:expr $(, reads_input: $reads_input:expr)?) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_info() -> $crate::plugin::Info {
+            let info = $crate::plugin::Info::new($day, $parts);
+            $(let info = $crate::plugin::Info { reads_input: $reads_input, ..info };)?
+            info
+        }
+
+        /// # Safety
+        ///
+        /// See `aoc::plugin`.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_solve(
+            part: u8,
+            input: *const u8,
+            len: usize,
+            answer: *mut *mut ::std::os::raw::c_char,
+        ) -> i32 {
+            $crate::plugin::solve($solve, part, input, len, answer)
+        }
+
+        /// # Safety
+        ///
+        /// See `aoc::plugin`.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_free(answer: *mut ::std::os::raw::c_char) {
+            $crate::plugin::free(answer)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn solver(part: u8, lines: &[String]) -> Option<String> {
+        match part {
+            1 => Some(lines.len().to_string()),
+            2 => panic!("not yet"),
+            _ => None,
+        }
+    }
+
+    unsafe fn call(part: u8, input: &[u8]) -> (i32, Option<String>) {
+        let mut answer = ptr::null_mut();
+        let status = solve(solver, part, input.as_ptr(), input.len(), &mut answer);
+        let text = if answer.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(answer).to_string_lossy().into_owned())
+        };
+
+        free(answer);
+        (status, text)
+    }
+
+    #[test]
+    fn test_solve() {
+        unsafe {
+            assert_eq!((OK, Some("3".to_string())), call(1, b"a\nb\nc\n"));
+            assert_eq!((OK, Some("1".to_string())), call(1, b""));
+            assert_eq!((FAILED, Some("not yet".to_string())), call(2, b"a"));
+            assert_eq!(NO_SUCH_PART, call(3, b"a").0);
+            assert_eq!(FAILED, call(1, b"\xff").0);
+        }
+    }
+}
@@ -0,0 +1,362 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Circular singly linked list stored in an arena.
+//!
+//! Nodes are indices into `next`, so moving a run of nodes elsewhere in the
+//! ring takes as long as the run, however long the ring is, like in the cup
+//! game from Day 23.
+//! Labels are small non-negative integers, and every label is in at most one
+//! node, which is found in O(1).
+
+use std::iter::FromIterator;
+
+const NONE: u32 = u32::MAX;
+
+
+#[derive(Clone,Copy,Debug,Eq,Hash,PartialEq)]
+pub struct Node(u32);
+
+/// Nodes taken out of the ring by `Ring::remove_after`, still linked to each
+/// other, until they are put back with `Ring::splice_after`, which consumes
+/// the chain.
+#[derive(Debug,PartialEq)]
+pub struct Chain {
+    first: Node,
+    last: Node,
+    len: usize,
+}
+
+impl Chain {
+    pub fn first(&self) -> Node {
+        self.first
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[derive(Clone,Debug,Default)]
+pub struct Ring {
+    next: Vec<u32>,
+    labels: Vec<u32>,
+    // Node of each label, or NONE
+    nodes: Vec<u32>,
+    // Whether each node is in a removed chain
+    detached: Vec<bool>,
+    len: usize,
+}
+
+impl Ring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preallocates room for `n` nodes, with labels up to `n`.
+    pub fn with_capacity(n: usize) -> Self {
+        Ring {
+            next: Vec::with_capacity(n),
+            labels: Vec::with_capacity(n),
+            nodes: Vec::with_capacity(n + 1),
+            detached: Vec::with_capacity(n),
+            len: 0,
+        }
+    }
+
+    /// Number of nodes in the ring, not counting removed chains.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The node with this label, if there's one (in the ring or in a removed
+    /// chain).
+    pub fn node(&self, label: u32) -> Option<Node> {
+        match self.nodes.get(label as usize) {
+            Some(&n) if n != NONE => Some(Node(n)),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self, node: Node) -> u32 {
+        self.labels[node.0 as usize]
+    }
+
+    pub fn next(&self, node: Node) -> Node {
+        Node(self.next[node.0 as usize])
+    }
+
+    fn new_node(&mut self, label: u32) -> Node {
+        assert!(self.node(label).is_none(), "Label {} is already in the ring", label);
+        assert!(self.labels.len() < NONE as usize, "The ring is full");
+
+        let node = self.labels.len() as u32;
+        if self.nodes.len() <= label as usize {
+            self.nodes.resize(label as usize + 1, NONE);
+        }
+
+        self.nodes[label as usize] = node;
+        self.labels.push(label);
+        self.next.push(node);
+        self.detached.push(false);
+        Node(node)
+    }
+
+    fn assert_in_ring(&self, at: Node) {
+        assert!(!self.detached[at.0 as usize], "Label {} is in a removed chain", self.label(at));
+    }
+
+    /// Adds a node with `label` right after `at`, which must be in the ring.
+    pub fn insert_after(&mut self, at: Node, label: u32) -> Node {
+        self.assert_in_ring(at);
+        let node = self.new_node(label);
+
+        self.next[node.0 as usize] = self.next[at.0 as usize];
+        self.next[at.0 as usize] = node.0;
+        self.len += 1;
+
+        node
+    }
+
+    /// Takes the `k` nodes after `at` out of the ring. `at` itself can't be
+    /// taken, so `k` must be smaller than the ring.
+    pub fn remove_after(&mut self, at: Node, k: usize) -> Chain {
+        self.assert_in_ring(at);
+        assert!(k < self.len, "Can't remove {} nodes from a ring of {}", k, self.len);
+
+        let first = self.next(at);
+        let mut last = at;
+        for _ in 0..k {
+            last = self.next(last);
+            self.detached[last.0 as usize] = true;
+        }
+
+        self.next[at.0 as usize] = self.next[last.0 as usize];
+        self.len -= k;
+
+        Chain { first, last, len: k }
+    }
+
+    /// Puts a removed chain back right after `at`.
+    pub fn splice_after(&mut self, at: Node, chain: Chain) {
+        self.assert_in_ring(at);
+        if chain.is_empty() {
+            return;
+        }
+
+        assert!(
+            self.detached[chain.first.0 as usize],
+            "Label {} is already back in the ring", self.label(chain.first),
+        );
+
+        let mut node = chain.first;
+        for _ in 0..chain.len {
+            self.detached[node.0 as usize] = false;
+            node = self.next(node);
+        }
+
+        self.next[chain.last.0 as usize] = self.next[at.0 as usize];
+        self.next[at.0 as usize] = chain.first.0;
+        self.len += chain.len;
+    }
+
+    /// Labels of the nodes in a removed chain, in order.
+    pub fn chain_labels(&self, chain: &Chain) -> impl Iterator<Item = u32> + '_ {
+        self.walk(chain.first, chain.len)
+    }
+
+    /// Labels of every node in the ring, starting at `node`.
+    pub fn iter_from(&self, node: Node) -> impl Iterator<Item = u32> + '_ {
+        self.walk(node, self.len)
+    }
+
+    fn walk(&self, node: Node, n: usize) -> impl Iterator<Item = u32> + '_ {
+        let mut current = node;
+
+        (0..n).map(move |_| {
+            let label = self.label(current);
+            current = self.next(current);
+            label
+        })
+    }
+}
+
+impl FromIterator<u32> for Ring {
+    /// A ring with the labels in order, where the last one links back to the
+    /// first.
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut ring = Ring::with_capacity(iter.size_hint().0);
+
+        if let Some(label) = iter.next() {
+            // A single node is its own successor
+            let mut last = ring.new_node(label);
+            ring.len = 1;
+
+            for label in iter {
+                last = ring.insert_after(last, label);
+            }
+        }
+
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn labels(ring: &Ring, start: u32) -> Vec<u32> {
+        ring.iter_from(ring.node(start).unwrap()).collect()
+    }
+
+    // The cup game from Day 23
+    fn crab_cups(ring: &mut Ring, start: u32, moves: usize) {
+        let max = ring.len() as u32;
+        let mut current = ring.node(start).unwrap();
+
+        for _ in 0..moves {
+            let picked = ring.remove_after(current, 3);
+            let mut destination = ring.label(current);
+
+            loop {
+                destination = if destination == 1 { max } else { destination - 1 };
+                if !ring.chain_labels(&picked).any(|l| l == destination) {
+                    break;
+                }
+            }
+
+            ring.splice_after(ring.node(destination).unwrap(), picked);
+            current = ring.next(current);
+        }
+    }
+
+    fn cups(labels: &[u32], n: u32) -> Ring {
+        labels.iter().copied().chain(labels.len() as u32 + 1..=n).collect()
+    }
+
+    #[test]
+    fn test_lookup() {
+        let ring: Ring = vec![3, 8, 9, 1].into_iter().collect();
+
+        assert_eq!(4, ring.len());
+        assert_eq!(vec![9, 1, 3, 8], labels(&ring, 9));
+        assert_eq!(3, ring.label(ring.next(ring.node(1).unwrap())));
+        assert_eq!(None, ring.node(2));
+        assert_eq!(None, ring.node(100));
+
+        let single: Ring = vec![7].into_iter().collect();
+        assert_eq!(vec![7], labels(&single, 7));
+        assert!(Ring::new().is_empty());
+    }
+
+    #[test]
+    fn test_remove_and_splice() {
+        let mut ring: Ring = vec![3, 8, 9, 1, 2, 5, 4, 6, 7].into_iter().collect();
+        let three = ring.node(3).unwrap();
+
+        let picked = ring.remove_after(three, 3);
+        assert_eq!(vec![8, 9, 1], ring.chain_labels(&picked).collect::<Vec<_>>());
+        assert_eq!(vec![3, 2, 5, 4, 6, 7], labels(&ring, 3));
+        assert_eq!(6, ring.len());
+
+        ring.splice_after(ring.node(2).unwrap(), picked);
+        assert_eq!(vec![3, 2, 8, 9, 1, 5, 4, 6, 7], labels(&ring, 3));
+
+        // Everything but the node itself
+        let rest = ring.remove_after(three, 8);
+        assert_eq!(vec![3], labels(&ring, 3));
+        ring.splice_after(three, rest);
+        assert_eq!(vec![3, 2, 8, 9, 1, 5, 4, 6, 7], labels(&ring, 3));
+
+        ring.insert_after(ring.node(7).unwrap(), 10);
+        assert_eq!(vec![7, 10, 3, 2, 8, 9, 1, 5, 4, 6], labels(&ring, 7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Label 8 is already in the ring")]
+    fn test_duplicate_label() {
+        let mut ring: Ring = vec![3, 8, 9].into_iter().collect();
+        ring.insert_after(ring.node(3).unwrap(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Label 8 is in a removed chain")]
+    fn test_insert_into_chain() {
+        let mut ring: Ring = vec![3, 8, 9, 1].into_iter().collect();
+        let picked = ring.remove_after(ring.node(3).unwrap(), 2);
+
+        ring.insert_after(picked.first(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Label 8 is already back in the ring")]
+    fn test_splice_twice() {
+        let mut ring: Ring = vec![3, 8, 9, 1].into_iter().collect();
+        let picked = ring.remove_after(ring.node(3).unwrap(), 2);
+        let again = Chain { first: picked.first, last: picked.last, len: picked.len };
+
+        ring.splice_after(ring.node(1).unwrap(), picked);
+        ring.splice_after(ring.node(3).unwrap(), again);
+    }
+
+    #[test]
+    fn test_crab_cups() {
+        let mut ring = cups(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 9);
+        crab_cups(&mut ring, 3, 100);
+        assert_eq!(vec![1, 6, 7, 3, 8, 4, 5, 2, 9], labels(&ring, 1));
+    }
+
+    // cargo test --release -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_ten_million_nodes() {
+        let start = Instant::now();
+        let mut ring = cups(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 10_000_000);
+        println!("built 10M nodes in {:.1?}", start.elapsed());
+
+        let start = Instant::now();
+        crab_cups(&mut ring, 3, 10_000_000);
+        println!("played 10M moves in {:.1?}", start.elapsed());
+        assert_eq!(10_000_000, ring.len());
+
+        let start = Instant::now();
+        assert_eq!(10_000_000, ring.iter_from(ring.node(1).unwrap()).count());
+        println!("walked 10M nodes in {:.1?}", start.elapsed());
+
+        // The example from Day 23, part 2
+        let mut ring = cups(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 1_000_000);
+        crab_cups(&mut ring, 3, 10_000_000);
+        let one = ring.node(1).unwrap();
+        let (a, b) = (ring.next(one), ring.next(ring.next(one)));
+        assert_eq!(149245887792, ring.label(a) as u64 * ring.label(b) as u64);
+    }
+}
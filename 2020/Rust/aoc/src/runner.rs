@@ -0,0 +1,534 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Common entry point for the solutions.
+//!
+//! Every day builds a `Runner`, loads its input and hands each part over to
+//! `Runner::part`, which prints the answer. On the command line:
+//!
+//! - `--profile` adds an allocation report per part (see `aoc::alloc`);
+//! - `--explain` prints the narrative recorded by the solver (see
+//!   `aoc::explain`);
+//! - `--cache` reuses the answers and the inputs parsed through
+//!   `Runner::parsed` from previous runs (see `aoc::cache`).
+//!
+//! A part may be solved in several ways, registered with `Runner::variants`.
+//! `--variant NAME` picks one of them, while `--cross-check` runs all of them,
+//! comparing their answers and running times.
+//!
+//! Puzzle parameters are loaded through `Runner::params` (see `aoc::config`),
+//! and `--max-steps` and `--timeout` limit the loops that might never end
+//! (see `aoc::budget`).
+
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::alloc::{self, Probe, Stats};
+use crate::budget::{self, DidNotConverge, Limits};
+use crate::cache::{self, Cache, InputKey, Key};
+use crate::config::{self, Params};
+use crate::explain;
+
+
+#[derive(Debug,Default,PartialEq)]
+pub struct Options {
+    pub profile: bool,
+    pub explain: bool,
+    pub cache: bool,
+    pub config: Option<String>,
+    pub overrides: Vec<(String, String)>,
+    pub limits: Limits,
+    pub variant: Option<String>,
+    pub cross_check: bool,
+}
+
+impl Options {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--profile" => options.profile = true,
+                "--explain" => options.explain = true,
+                "--cache" => options.cache = true,
+                "--cross-check" => options.cross_check = true,
+                "--variant" => {
+                    options.variant = Some(args.next().ok_or("--variant needs a name")?);
+                },
+                "--config" => {
+                    options.config = Some(args.next().ok_or("--config needs a path")?);
+                },
+                "--set" => {
+                    let s = args.next().ok_or("--set needs key=value")?;
+                    options.overrides.push(config::parse_override(&s)?);
+                },
+                "--max-steps" => {
+                    let n = args.next().ok_or("--max-steps needs a number")?;
+                    let n = n.parse::<u64>().map_err(|_| format!("Invalid number of steps: {}", n))?;
+                    options.limits.max_steps = Some(n);
+                },
+                "--timeout" => {
+                    let s = args.next().ok_or("--timeout needs a number of seconds")?;
+                    let secs = s.parse::<f64>()
+                        .ok()
+                        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                        .ok_or(format!("Invalid timeout: {}", s))?;
+                    options.limits.timeout = Some(Duration::from_secs_f64(secs));
+                },
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+pub struct Runner {
+    day: u8,
+    options: Options,
+    probe: Option<Probe>,
+    stats: Vec<(String, Stats)>,
+    has_params: bool,
+    has_variants: bool,
+    // Parts whose variants gave different answers
+    disagreements: Vec<u8>,
+}
+
+type Solver<'a> = Box<dyn FnOnce() -> Result<String, DidNotConverge> + 'a>;
+
+/// The ways of solving a part, in order of preference. The first one is used
+/// unless another one is picked with `--variant`.
+pub struct Variants<'r, 'a> {
+    runner: &'r mut Runner,
+    part: u8,
+    solvers: Vec<(&'static str, Solver<'a>)>,
+}
+
+impl<'r, 'a> Variants<'r, 'a> {
+    pub fn variant<T, F>(self, name: &'static str, f: F) -> Self
+    where
+        T: Display,
+        F: FnOnce() -> T + 'a,
+    {
+        self.try_variant(name, || Ok::<T, DidNotConverge>(f()))
+    }
+
+    pub fn try_variant<T, F>(mut self, name: &'static str, f: F) -> Self
+    where
+        T: Display,
+        F: FnOnce() -> Result<T, DidNotConverge> + 'a,
+    {
+        self.solvers.push((name, Box::new(|| f().map(|answer| answer.to_string()))));
+        self
+    }
+
+    pub fn run(mut self) {
+        if self.solvers.is_empty() {
+            eprintln!("Part {} has no variants to run", self.part);
+            process::exit(2);
+        }
+
+        if self.runner.options.cross_check {
+            return self.runner.cross_check(self.part, self.solvers);
+        }
+
+        let i = match self.runner.options.variant.as_deref() {
+            None => 0,
+            Some(name) => match self.solvers.iter().position(|(n, _)| *n == name) {
+                Some(i) => i,
+                None => {
+                    let names: Vec<&str> = self.solvers.iter().map(|(n, _)| *n).collect();
+                    eprintln!(
+                        "Part {} has no variant {} (try {})",
+                        self.part, name, names.join(", "),
+                    );
+                    process::exit(2);
+                },
+            },
+        };
+
+        let (name, solver) = self.solvers.swap_remove(i);
+        self.runner.solve(self.part, Some(name), solver);
+    }
+}
+
+impl Runner {
+    /// Builds a runner from the process arguments. It should be created
+    /// before reading the input, so that parsing shows up in the report.
+    pub fn new(day: u8) -> Self {
+        match Options::parse(env::args().skip(1)) {
+            Ok(options) => Runner::with_options(day, options),
+            Err(e) => {
+                eprintln!("{}", e);
+                eprintln!(
+                    "Usage: Day{} [--profile] [--explain] [--cache] [--config PATH] \
+                     [--set KEY=VALUE]... [--max-steps N] [--timeout SECS] \
+                     [--variant NAME | --cross-check]",
+                    day,
+                );
+                process::exit(2);
+            },
+        }
+    }
+
+    pub fn with_options(day: u8, options: Options) -> Self {
+        if options.profile && !alloc::ENABLED {
+            eprintln!("Allocation profiling needs a build with `--features profile`");
+        }
+
+        if options.explain {
+            explain::enable();
+        }
+
+        budget::set_limits(options.limits);
+
+        Runner {
+            day,
+            options,
+            probe: Some(Probe::start()),
+            stats: Vec::new(),
+            has_params: false,
+            has_variants: false,
+            disagreements: Vec::new(),
+        }
+    }
+
+    /// Loads the puzzle parameters from `aoc.toml` (or the file given with
+    /// `--config`) and the `--set` overrides. Invalid parameters end the
+    /// process.
+    pub fn params<P: Params>(&mut self) -> P {
+        self.has_params = true;
+
+        let path = self.options.config.as_deref().unwrap_or("aoc.toml");
+        let file = match fs::read_to_string(path) {
+            Ok(contents) => Some(contents),
+            Err(_) if self.options.config.is_none() && !Path::new(path).exists() => None,
+            Err(e) => {
+                eprintln!("Couldn't read {}: {}", path, e);
+                process::exit(2);
+            },
+        };
+
+        match config::load(file.as_deref(), &self.options.overrides) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("Invalid parameters: {}", e);
+                process::exit(2);
+            },
+        }
+    }
+
+    /// Parses the input with `parse`, or with `--cache`, loads what an earlier
+    /// run of the same build parsed from the same input. The input has to be
+    /// read through `aoc::fs` beforehand, since that is what tells inputs
+    /// apart. `name` tells apart the inputs parsed by a day.
+    pub fn parsed<T, F>(&mut self, name: &str, parse: F) -> T
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> T,
+    {
+        if !self.options.cache {
+            return parse();
+        }
+
+        let cache = Cache::new("target/aoc-cache");
+        let key = InputKey {
+            day: self.day,
+            name,
+            inputs: cache::inputs(),
+            version: cache::solver_version(),
+        };
+
+        if self.reads_cache() {
+            if let Some(parsed) = cache.get_parsed(&key) {
+                return parsed;
+            }
+        }
+
+        let parsed = parse();
+        if let Err(e) = cache.put_parsed(&key, &parsed) {
+            eprintln!("Couldn't cache the parsed {}: {}", name, e);
+        }
+
+        parsed
+    }
+
+    fn profiling(&self) -> bool {
+        self.options.profile && alloc::ENABLED
+    }
+
+    // Cached answers would hide what explaining and profiling are after,
+    // although they still get stored.
+    fn reads_cache(&self) -> bool {
+        self.options.cache && !self.options.explain && !self.options.profile
+    }
+
+    fn cache_key(&self, part: u8, variant: Option<&str>) -> Key {
+        let mut params = DefaultHasher::new();
+        let path = self.options.config.as_deref().unwrap_or("aoc.toml");
+        fs::read_to_string(path).ok().hash(&mut params);
+        self.options.overrides.hash(&mut params);
+        variant.hash(&mut params);
+
+        Key {
+            day: self.day,
+            part,
+            inputs: cache::inputs(),
+            params: params.finish(),
+            version: cache::solver_version(),
+        }
+    }
+
+    /// Solves one part of the puzzle and prints its answer, followed by the
+    /// narrative recorded so far when explaining.
+    pub fn part<T: Display, F: FnOnce() -> T>(&mut self, part: u8, f: F) {
+        self.try_part(part, || Ok::<T, DidNotConverge>(f()));
+    }
+
+    /// Like `part`, for solvers that may give up on their budget. The partial
+    /// state is printed in place of the answer.
+    pub fn try_part<T, F>(&mut self, part: u8, f: F)
+    where
+        T: Display,
+        F: FnOnce() -> Result<T, DidNotConverge>,
+    {
+        self.solve(part, None, || f().map(|answer| answer.to_string()));
+    }
+
+    /// Registers several ways of solving a part, e.g.
+    /// `runner.variants(2).variant("naive", || ...).variant("fast", || ...).run()`.
+    pub fn variants<'r, 'a>(&'r mut self, part: u8) -> Variants<'r, 'a> {
+        self.has_variants = true;
+        Variants { runner: self, part, solvers: Vec::new() }
+    }
+
+    // Whatever happened between creating the runner and solving the first
+    // part is accounted as input handling.
+    fn stop_input_probe(&mut self) {
+        if let Some(probe) = self.probe.take() {
+            self.stats.push(("input".to_string(), probe.stop()));
+        }
+    }
+
+    fn solve<F>(&mut self, part: u8, variant: Option<&str>, f: F)
+    where
+        F: FnOnce() -> Result<String, DidNotConverge>,
+    {
+        self.stop_input_probe();
+
+        let cache = Cache::new("target/aoc-cache");
+        let key = if self.options.cache { Some(self.cache_key(part, variant)) } else { None };
+
+        if self.reads_cache() {
+            if let Some(answer) = key.and_then(|k| cache.get(&k)) {
+                println!("Day {} / Part {}: {} (cached)", self.day, part, answer);
+                return;
+            }
+        }
+
+        budget::start_clock();
+        let (answer, stats) = alloc::measure(f);
+        self.stats.push((format!("part {}", part), stats));
+
+        match answer {
+            Ok(answer) => {
+                println!("Day {} / Part {}: {}", self.day, part, answer);
+
+                if let Some(key) = key {
+                    if let Err(e) = cache.put(&key, &answer) {
+                        eprintln!("Couldn't cache the answer: {}", e);
+                    }
+                }
+            },
+            Err(e) => {
+                println!("Day {} / Part {}: {}", self.day, part, e);
+                for line in e.state.lines() {
+                    println!("    {}", line);
+                }
+            },
+        }
+
+        for record in explain::take() {
+            println!("    {}", record);
+        }
+    }
+
+    // Runs every variant, printing their answers along with how long they
+    // took. Answers that don't match are reported when finishing.
+    fn cross_check(&mut self, part: u8, solvers: Vec<(&'static str, Solver<'_>)>) {
+        self.stop_input_probe();
+
+        let mut rows: Vec<(&str, String, Duration)> = Vec::new();
+
+        for (name, solver) in solvers {
+            budget::start_clock();
+            let start = Instant::now();
+            let (answer, stats) = alloc::measure(solver);
+            let elapsed = start.elapsed();
+
+            self.stats.push((format!("part {} {}", part, name), stats));
+            rows.push((name, answer.unwrap_or_else(|e| e.to_string()), elapsed));
+        }
+
+        let agree = rows.windows(2).all(|w| w[0].1 == w[1].1);
+        if agree {
+            println!("Day {} / Part {}: {}", self.day, part, rows[0].1);
+        } else {
+            println!("Day {} / Part {}: variants disagree", self.day, part);
+            self.disagreements.push(part);
+        }
+
+        let width = rows.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+        for (name, answer, elapsed) in rows {
+            println!("    {:<w$}  {:>12.1?}  {}", name, elapsed, answer, w = width);
+        }
+
+        for record in explain::take() {
+            println!("    {}", record);
+        }
+    }
+
+    /// Prints the allocation report, if one was requested. Ends the process
+    /// with an error if variants of a part disagreed.
+    pub fn finish(self) {
+        if !self.has_params && (self.options.config.is_some() || !self.options.overrides.is_empty()) {
+            eprintln!("Day {} has no parameters, --config and --set were ignored", self.day);
+        }
+
+        if !self.has_variants && (self.options.variant.is_some() || self.options.cross_check) {
+            eprintln!("Day {} has no variants, --variant and --cross-check were ignored", self.day);
+        }
+
+        if self.profiling() {
+            println!();
+            println!("{}", self.report());
+        }
+
+        if !self.disagreements.is_empty() {
+            eprintln!("Variants disagree on part(s) {:?}", self.disagreements);
+            process::exit(1);
+        }
+    }
+
+    fn report(&self) -> String {
+        let total = self.stats
+            .iter()
+            .fold(Stats::default(), |acc, (_, s)| acc.merge(s));
+        let mut lines = vec![
+            format!("{:<10} {:>12} {:>14} {:>14}", format!("Day {}", self.day), "allocs", "bytes", "peak"),
+        ];
+
+        for (name, s) in self.stats.iter().chain(Some(&("total".to_string(), total))) {
+            lines.push(format!(
+                "{:<10} {:>12} {:>14} {:>14}",
+                name, s.allocations, s.bytes, s.peak,
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(Ok(Options::default()), Options::parse(args(&[])));
+        assert_eq!(
+            Ok(Options { profile: true, explain: true, ..Default::default() }),
+            Options::parse(args(&["--explain", "--profile"])),
+        );
+        assert_eq!(
+            Ok(Options {
+                config: Some("other.toml".to_string()),
+                overrides: vec![("preamble".to_string(), "5".to_string())],
+                ..Default::default()
+            }),
+            Options::parse(args(&["--config", "other.toml", "--set", "preamble=5"])),
+        );
+        assert!(Options::parse(args(&["--set"])).is_err());
+        assert_eq!(
+            Ok(Limits { max_steps: Some(100), timeout: Some(Duration::from_millis(1500)) }),
+            Options::parse(args(&["--max-steps", "100", "--timeout", "1.5"])).map(|o| o.limits),
+        );
+        assert!(Options::parse(args(&["--timeout", "-1"])).is_err());
+        assert!(Options::parse(args(&["--wat"])).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let mut runner = Runner::with_options(1, Options { profile: true, ..Default::default() });
+        runner.part(1, || 42);
+        runner.part(2, || vec![1, 2, 3].len());
+
+        let report = runner.report();
+        let names: Vec<&str> = report
+            .lines()
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect();
+
+        assert_eq!(vec!["Day", "input", "part", "part", "total"], names);
+    }
+
+    #[test]
+    fn test_variants() {
+        use std::cell::Cell;
+
+        let ran = Cell::new("");
+        let mut runner = Runner::with_options(
+            1,
+            Options { variant: Some("fast".to_string()), ..Default::default() },
+        );
+        runner.variants(1)
+            .variant("naive", || { ran.set("naive"); 42 })
+            .variant("fast", || { ran.set("fast"); 42 })
+            .run();
+        assert_eq!("fast", ran.get());
+
+        let mut runner = Runner::with_options(1, Options { cross_check: true, ..Default::default() });
+        runner.variants(1).variant("a", || 42).variant("b", || 42).run();
+        runner.variants(2)
+            .variant("a", || 42)
+            .try_variant("b", || Err::<usize, _>(DidNotConverge {
+                reason: "stuck".to_string(),
+                state: String::new(),
+            }))
+            .run();
+        assert_eq!(vec![2], runner.disagreements);
+    }
+}
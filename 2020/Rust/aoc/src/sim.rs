@@ -0,0 +1,273 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Step-based systems.
+//!
+//! Anything that evolves one step at a time (cellular automata, card games,
+//! ...) can implement `Simulation` and get fixed-point detection, cycle
+//! detection and fast-forwarding for free. States are compared for full
+//! equality, never just by some summary of them.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::budget::{Budget, Exhausted};
+
+
+pub trait Simulation: Clone + Eq + Hash {
+    /// Advances the system by one step.
+    fn step(&mut self);
+
+    /// Hash of the current state. Two states with different fingerprints
+    /// are never equal, which makes it a cheap first check.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        self.fingerprint() == other.fingerprint() && self == other
+    }
+
+    /// Steps until a step leaves the state unchanged. Returns the number of
+    /// steps that actually changed something.
+    fn run_until_fixed_point(&mut self) -> usize {
+        match self.run_until_fixed_point_within(&mut Budget::unlimited()) {
+            Ok(steps) => steps,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Like `run_until_fixed_point`, but gives up once the budget runs out,
+    /// leaving the system wherever it got to.
+    fn run_until_fixed_point_within(&mut self, budget: &mut Budget) -> Result<usize, Exhausted> {
+        let mut steps = 0;
+
+        loop {
+            budget.tick()?;
+            let previous = self.clone();
+            self.step();
+
+            if self.same_state(&previous) {
+                return Ok(steps);
+            }
+            steps += 1;
+        }
+    }
+
+    /// Steps until the system gets back to a state it has already been in,
+    /// remembering every state along the way. Returns `(prefix, period)`
+    /// like `find_cycle`, but each step is only simulated once, and the
+    /// system is left in the repeated state.
+    ///
+    /// Prefer this over `find_cycle` when steps are expensive and the
+    /// states are small enough to keep around.
+    fn run_until_repeat(&mut self) -> (usize, usize) {
+        let mut seen: HashMap<Self, usize> = HashMap::new();
+        let mut steps = 0;
+
+        loop {
+            if let Some(&i) = seen.get(self) {
+                return (i, steps - i);
+            }
+
+            seen.insert(self.clone(), steps);
+            self.step();
+            steps += 1;
+        }
+    }
+
+    /// Brent's cycle detection. Returns `(prefix, period)`: the number of
+    /// steps before the first state that belongs to the cycle and the
+    /// length of the cycle. A fixed point is a cycle of period 1.
+    ///
+    /// Never returns for systems that don't eventually repeat themselves.
+    fn find_cycle(&self) -> (usize, usize) {
+        match brent(self, None) {
+            Ok(cycle) => cycle,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// The state after `n` steps. If the system starts repeating itself
+    /// before that, the cycle is skipped over instead of being simulated.
+    fn after(&self, n: usize) -> Self {
+        let (prefix, period) = match brent(self, Some(n)) {
+            Ok(cycle) => cycle,
+            Err(state) => return state,
+        };
+
+        let steps = if n <= prefix { n } else { prefix + (n - prefix) % period };
+        let mut state = self.clone();
+        for _ in 0..steps {
+            state.step();
+        }
+
+        state
+    }
+}
+
+// When `limit` is reached before finding a cycle, the state after `limit`
+// steps is returned as an error.
+fn brent<S: Simulation>(start: &S, limit: Option<usize>) -> Result<(usize, usize), S> {
+    let mut power = 1;
+    let mut period = 1;
+    let mut steps = 1;
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+
+    if limit == Some(0) {
+        return Err(hare);
+    }
+    hare.step();
+
+    while !tortoise.same_state(&hare) {
+        if limit == Some(steps) {
+            return Err(hare);
+        }
+
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        hare.step();
+        steps += 1;
+        period += 1;
+    }
+
+    // Finds the first repetition by moving two states `period` apart.
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..period {
+        hare.step();
+    }
+
+    let mut prefix = 0;
+    while !tortoise.same_state(&hare) {
+        tortoise.step();
+        hare.step();
+        prefix += 1;
+    }
+
+    Ok((prefix, period))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Walks a functional graph given by `next`, counting the steps taken.
+    #[derive(Clone,Debug)]
+    struct Walk {
+        node: usize,
+        next: Vec<usize>,
+        steps: usize,
+    }
+
+    impl PartialEq for Walk {
+        fn eq(&self, other: &Self) -> bool {
+            self.node == other.node
+        }
+    }
+
+    impl Eq for Walk {}
+
+    impl Hash for Walk {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.node.hash(state);
+        }
+    }
+
+    impl Simulation for Walk {
+        fn step(&mut self) {
+            self.node = self.next[self.node];
+            self.steps += 1;
+        }
+    }
+
+    fn walk(next: Vec<usize>) -> Walk {
+        Walk { node: 0, next, steps: 0 }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2
+        assert_eq!((2, 4), walk(vec![1, 2, 3, 4, 5, 2]).find_cycle());
+        // 0 -> 0
+        assert_eq!((0, 1), walk(vec![0]).find_cycle());
+        // 0 -> 1 -> 2 -> 0
+        assert_eq!((0, 3), walk(vec![1, 2, 0]).find_cycle());
+    }
+
+    #[test]
+    fn test_run_until_repeat() {
+        let mut w = walk(vec![1, 2, 3, 4, 5, 2]);
+
+        assert_eq!((2, 4), w.run_until_repeat());
+        assert_eq!(2, w.node);
+        assert_eq!(6, w.steps);
+    }
+
+    #[test]
+    fn test_run_until_fixed_point() {
+        let mut w = walk(vec![1, 2, 3, 3]);
+
+        assert_eq!(3, w.run_until_fixed_point());
+        assert_eq!(3, w.node);
+
+        let mut c = Counter(0);
+        assert_eq!(Err(Exhausted::Steps(10)), c.run_until_fixed_point_within(&mut Budget::with_max_steps(10)));
+        assert_eq!(Counter(10), c);
+    }
+
+    #[test]
+    fn test_after_skips_cycles() {
+        let w = walk(vec![1, 2, 3, 4, 5, 2]);
+
+        assert_eq!(0, w.after(0).node);
+        assert_eq!(1, w.after(1).node);
+        assert_eq!(5, w.after(5).node);
+        assert_eq!(2, w.after(6).node);
+
+        let far = w.after(1_000_000_000_003);
+        assert_eq!(3, far.node);
+        assert!(far.steps < 100);
+    }
+
+    #[derive(Clone,Debug,Eq,Hash,PartialEq)]
+    struct Counter(u64);
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn test_after_without_cycle() {
+        assert_eq!(Counter(1000), Counter(0).after(1000));
+    }
+}
@@ -0,0 +1,459 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Offline analysis of a private leaderboard export.
+//!
+//! Reads the JSON from the leaderboard's "API" link, saved to disk, and shows
+//! for every day when each member got their stars, how long part 2 took them
+//! after part 1, and how the local ranking moved. Our own solutions' running
+//! times are shown next to each day.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::watch;
+
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub path: PathBuf,
+    // Prints this member's star timeline instead of the days
+    pub member: Option<String>,
+    pub runtimes: bool,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut path = None;
+        let mut member = None;
+        let mut runtimes = true;
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--member" => member = Some(iter.next().ok_or("--member needs a value")?.clone()),
+                "--no-runtimes" => runtimes = false,
+                _ if arg.starts_with("--") => return Err(format!("Unknown argument: {}", arg)),
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => return Err(format!("Unexpected argument: {}", arg)),
+            }
+        }
+
+        Ok(Self {
+            path: path.ok_or("Missing the leaderboard file")?,
+            member,
+            runtimes,
+        })
+    }
+}
+
+// Older exports have ids and timestamps as strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Int(i64),
+    Text(String),
+}
+
+impl Number {
+    fn value(&self) -> Result<i64, String> {
+        match self {
+            Number::Int(n) => Ok(*n),
+            Number::Text(s) => s.parse().map_err(|_| format!("Not a number: {}", s)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Export {
+    event: String,
+    members: HashMap<String, ExportMember>,
+}
+
+#[derive(Deserialize)]
+struct ExportMember {
+    id: Number,
+    name: Option<String>,
+    #[serde(default)]
+    completion_day_level: HashMap<String, HashMap<String, ExportStar>>,
+}
+
+#[derive(Deserialize)]
+struct ExportStar {
+    get_star_ts: Number,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Member {
+    pub id: i64,
+    pub name: String,
+    // When they got each star, by (day, part), in seconds since the epoch
+    pub stars: BTreeMap<(u8, u8), i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Leaderboard {
+    pub event: i64,
+    // Sorted by id
+    pub members: Vec<Member>,
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, String> {
+    let export: Export = serde_json::from_str(json)
+        .map_err(|e| format!("Not a leaderboard export: {}", e))?;
+    let event = export.event.parse().map_err(|_| format!("Invalid event: {}", export.event))?;
+    let mut members = Vec::new();
+
+    for m in export.members.values() {
+        let id = m.id.value()?;
+        let mut stars = BTreeMap::new();
+
+        for (day, parts) in m.completion_day_level.iter() {
+            let day: u8 = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+            for (part, star) in parts.iter() {
+                let part: u8 = part.parse().map_err(|_| format!("Invalid part: {}", part))?;
+                stars.insert((day, part), star.get_star_ts.value()?);
+            }
+        }
+
+        members.push(Member {
+            id,
+            name: m.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", id)),
+            stars,
+        });
+    }
+
+    members.sort_by_key(|m| m.id);
+    Ok(Leaderboard { event, members })
+}
+
+// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    (if m <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 }, m, d)
+}
+
+/// Puzzles unlock at midnight EST (UTC-5).
+pub fn unlock(event: i64, day: u8) -> i64 {
+    (days_from_civil(event, 12, day as i64) * 86400) + 5 * 3600
+}
+
+fn timestamp(ts: i64) -> String {
+    let (y, m, d) = civil_from_days(ts.div_euclid(86400));
+    let s = ts.rem_euclid(86400);
+
+    format!("{}-{:02}-{:02} {:02}:{:02}:{:02} UTC", y, m, d, s / 3600, s / 60 % 60, s % 60)
+}
+
+fn duration(secs: i64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Row {
+    // Index into `Leaderboard::members`
+    pub member: usize,
+    pub rank: usize,
+    // Positions gained since the previous day, if they were ranked then
+    pub change: Option<i64>,
+    pub score: usize,
+    // Seconds since the puzzle unlocked
+    pub part1: Option<i64>,
+    pub part2: Option<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Standing {
+    pub day: u8,
+    pub rows: Vec<Row>,
+}
+
+/// The local ranking at the end of every day that has stars. Each star is
+/// worth as many points as there are members, minus the ones who got it
+/// first.
+pub fn standings(lb: &Leaderboard) -> Vec<Standing> {
+    let n = lb.members.len();
+    let last_day = lb.members.iter().flat_map(|m| m.stars.keys()).map(|k| k.0).max();
+    let mut scores = vec![0; n];
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut res = Vec::new();
+
+    for day in 1..=last_day.unwrap_or(0) {
+        for part in 1..=2 {
+            let mut solvers: Vec<(i64, usize)> = lb.members
+                .iter()
+                .enumerate()
+                .filter_map(|(i, m)| Some((*m.stars.get(&(day, part))?, i)))
+                .collect();
+
+            solvers.sort();
+            for (position, (_, i)) in solvers.into_iter().enumerate() {
+                scores[i] += n - position;
+            }
+        }
+
+        // Ties go to whoever got their last star first
+        let last_star = |i: usize| lb.members[i].stars
+            .iter()
+            .filter(|((d, _), _)| *d <= day)
+            .map(|(_, ts)| *ts)
+            .max();
+        let mut ranked: Vec<usize> = (0..n).filter(|&i| last_star(i).is_some()).collect();
+        ranked.sort_by_key(|&i| (std::cmp::Reverse(scores[i]), last_star(i), lb.members[i].id));
+
+        let rows: Vec<Row> = ranked
+            .iter()
+            .enumerate()
+            .map(|(position, &i)| {
+                let since_unlock = |part| {
+                    lb.members[i].stars.get(&(day, part)).map(|ts| ts - unlock(lb.event, day))
+                };
+
+                Row {
+                    member: i,
+                    rank: position + 1,
+                    change: previous.get(&i).map(|&r| r as i64 - (position + 1) as i64),
+                    score: scores[i],
+                    part1: since_unlock(1),
+                    part2: since_unlock(2),
+                }
+            })
+            .collect();
+
+        previous = rows.iter().map(|r| (r.member, r.rank)).collect();
+        if rows.iter().any(|r| r.part1.is_some() || r.part2.is_some()) {
+            res.push(Standing { day, rows });
+        }
+    }
+
+    res
+}
+
+fn render_standing(lb: &Leaderboard, standing: &Standing, ours: Option<&str>) -> String {
+    let width = standing.rows
+        .iter()
+        .map(|r| lb.members[r.member].name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let time = |t: Option<i64>| t.map(duration).unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![match ours {
+        Some(ours) => format!("Day {} (ours: {})", standing.day, ours),
+        None => format!("Day {}", standing.day),
+    }];
+    lines.push(format!(
+        "  {:>3}  {:>4}  {:<w$}  {:>10}  {:>10}  {:>10}  {:>5}",
+        "#", "move", "member", "part 1", "part 2", "delta", "score", w = width,
+    ));
+
+    for row in standing.rows.iter() {
+        let change = match row.change {
+            None => "new".to_string(),
+            Some(0) => "=".to_string(),
+            Some(c) => format!("{:+}", c),
+        };
+        let delta = match (row.part1, row.part2) {
+            (Some(p1), Some(p2)) => Some(p2 - p1),
+            _ => None,
+        };
+
+        lines.push(format!(
+            "  {:>3}  {:>4}  {:<w$}  {:>10}  {:>10}  {:>10}  {:>5}",
+            row.rank, change, lb.members[row.member].name,
+            time(row.part1), time(row.part2), time(delta), row.score, w = width,
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_timeline(lb: &Leaderboard, member: &Member) -> String {
+    let mut stars: Vec<(i64, (u8, u8))> = member.stars.iter().map(|(k, ts)| (*ts, *k)).collect();
+    stars.sort();
+
+    let mut lines = vec![format!("{}: {} stars", member.name, stars.len())];
+    for (count, (ts, (day, part))) in stars.into_iter().enumerate() {
+        lines.push(format!(
+            "  {}  Day {:>2} part {}  {:>10} after unlock  ({} stars)",
+            timestamp(ts), day, part, duration(ts - unlock(lb.event, day)), count + 1,
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// Builds and runs each of our days, for its running time
+fn runtimes(days: &[u8]) -> HashMap<u8, String> {
+    days.iter()
+        .map(|&day| {
+            let dir = watch::day_dir(day);
+            let ours = if !dir.is_dir() {
+                "not solved".to_string()
+            } else {
+                match watch::solve(&dir, day, &[]) {
+                    Ok(outcome) => format!("{:.1?}", outcome.elapsed),
+                    Err(e) => e,
+                }
+            };
+
+            (day, ours)
+        })
+        .collect()
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let json = fs::read_to_string(&options.path)
+        .map_err(|e| format!("Couldn't read {}: {}", options.path.display(), e))?;
+    let lb = parse(&json)?;
+
+    if let Some(name) = &options.member {
+        let member = lb.members
+            .iter()
+            .find(|m| &m.name == name || m.id.to_string() == *name)
+            .ok_or_else(|| format!("There's no member {}", name))?;
+
+        println!("{}", render_timeline(&lb, member));
+        return Ok(());
+    }
+
+    let standings = standings(&lb);
+    let ours: HashMap<u8, String> = if options.runtimes {
+        runtimes(&standings.iter().map(|s| s.day).collect::<Vec<_>>())
+    } else {
+        HashMap::new()
+    };
+
+    println!("Leaderboard {}, {} members", lb.event, lb.members.len());
+    for standing in standings.iter() {
+        println!();
+        println!("{}", render_standing(&lb, standing, ours.get(&standing.day).map(String::as_str)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 1 of 2020 unlocked at 1606798800, day 2 a day later
+    const EXPORT: &str = r#"{
+        "event": "2020",
+        "owner_id": "1",
+        "members": {
+            "1": {
+                "id": "1", "name": "Alice", "stars": 3, "local_score": 10,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": "1606799100"}, "2": {"get_star_ts": "1606799400"}},
+                    "2": {"1": {"get_star_ts": "1606889000"}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 4, "local_score": 11,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1606799200}, "2": {"get_star_ts": 1606799900}},
+                    "2": {"1": {"get_star_ts": 1606885500}, "2": {"get_star_ts": 1606886100}}
+                }
+            },
+            "3": {"id": 3, "name": "Carol", "stars": 0, "local_score": 0, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let lb = parse(EXPORT).unwrap();
+
+        assert_eq!(2020, lb.event);
+        assert_eq!(vec![1, 2, 3], lb.members.iter().map(|m| m.id).collect::<Vec<_>>());
+        assert_eq!("(anonymous user #2)", lb.members[1].name);
+        assert_eq!(Some(&1606799400), lb.members[0].stars.get(&(1, 2)));
+        assert_eq!(4, lb.members[1].stars.len());
+        assert!(parse("{\"event\": \"2020\"}").is_err());
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(1606798800, unlock(2020, 1));
+        assert_eq!(1606798800 + 86400 * 24, unlock(2020, 25));
+        assert_eq!("2020-12-01 05:05:00 UTC", timestamp(1606799100));
+        assert_eq!("2021-01-01 00:00:00 UTC", timestamp(1609459200));
+        assert_eq!("27:03:04", duration(27 * 3600 + 3 * 60 + 4));
+    }
+
+    #[test]
+    fn test_standings() {
+        let lb = parse(EXPORT).unwrap();
+        let standings = standings(&lb);
+
+        assert_eq!(2, standings.len());
+        assert_eq!(
+            Standing {
+                day: 1,
+                rows: vec![
+                    Row { member: 0, rank: 1, change: None, score: 6, part1: Some(300), part2: Some(600) },
+                    Row { member: 1, rank: 2, change: None, score: 4, part1: Some(400), part2: Some(1100) },
+                ],
+            },
+            standings[0],
+        );
+        // Anonymous gets both stars first, and overtakes Alice
+        assert_eq!(
+            Standing {
+                day: 2,
+                rows: vec![
+                    Row { member: 1, rank: 1, change: Some(1), score: 10, part1: Some(300), part2: Some(900) },
+                    Row { member: 0, rank: 2, change: Some(-1), score: 8, part1: Some(3800), part2: None },
+                ],
+            },
+            standings[1],
+        );
+        assert_eq!(
+            "Day 2 (ours: 1.5ms)\n\
+             \x20   #  move  member                   part 1      part 2       delta  score\n\
+             \x20   1    +1  (anonymous user #2)     0:05:00     0:15:00     0:10:00     10\n\
+             \x20   2    -1  Alice                   1:03:20           -           -      8",
+            render_standing(&lb, &standings[1], Some("1.5ms")),
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Tooling that works across days, e.g. `aoc watch --day 9`.
+
+use std::env;
+use std::process;
+
+mod leaderboard;
+mod plugins;
+mod watch;
+
+const USAGE: &str = "Usage: aoc watch --day N [--interval MS] [-- ARGS...]\n       \
+                     aoc leaderboard FILE [--member NAME] [--no-runtimes]\n       \
+                     aoc run --plugins DIR [--day N] [--input PATH]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("watch") => watch::Options::parse(&args[1..]).and_then(|o| watch::run(&o)),
+        Some("leaderboard") => {
+            leaderboard::Options::parse(&args[1..]).and_then(|o| leaderboard::run(&o))
+        },
+        Some("run") => plugins::Options::parse(&args[1..]).and_then(|o| plugins::run(&o)),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(2);
+    }
+}
@@ -0,0 +1,259 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Runs days built as plugins (see `aoc::plugin`).
+//!
+//! Every shared library in the plugins folder that exports the plugin entry
+//! points is loaded at runtime, so new days can be added without rebuilding
+//! `aoc`. Each one is handed the input from its day's `data` folder, unless
+//! another one is given with `--input`, or no input at all if the day has it
+//! in the code.
+
+use std::env;
+use std::ffi::CStr;
+use std::fs;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::time::Instant;
+
+use aoc::plugin::{self, FreeFn, Info, InfoFn, SolveFn};
+use libloading::Library;
+
+use crate::watch;
+
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub dir: PathBuf,
+    pub day: Option<u8>,
+    pub input: Option<PathBuf>,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut dir = None;
+        let mut day = None;
+        let mut input = None;
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--plugins" => dir = Some(PathBuf::from(iter.next().ok_or("--plugins needs a folder")?)),
+                "--day" => {
+                    let n = iter.next().ok_or("--day needs a value")?;
+                    day = Some(n.parse::<u8>().map_err(|_| format!("Invalid day: {}", n))?);
+                },
+                "--input" => input = Some(PathBuf::from(iter.next().ok_or("--input needs a file")?)),
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        if input.is_some() && day.is_none() {
+            return Err("--input needs --day".to_string());
+        }
+
+        Ok(Self {
+            dir: dir.ok_or("Missing --plugins")?,
+            day,
+            input,
+        })
+    }
+}
+
+pub struct Plugin {
+    pub path: PathBuf,
+    pub info: Info,
+    solve: SolveFn,
+    free: FreeFn,
+    // Keeps the functions above loaded
+    _library: Library,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let error = |e: libloading::Error| format!("{}: {}", path.display(), e);
+
+        // Loading runs the library's initialisers, and the symbols are only
+        // trusted once the ABI version matches.
+        unsafe {
+            let library = Library::new(path).map_err(error)?;
+            let info = (*library.get::<InfoFn>(plugin::INFO_SYMBOL).map_err(error)?)();
+
+            if info.abi_version != plugin::ABI_VERSION {
+                return Err(format!(
+                    "{}: built for plugin ABI {}, but this is ABI {}",
+                    path.display(), info.abi_version, plugin::ABI_VERSION,
+                ));
+            }
+
+            let solve = *library.get::<SolveFn>(plugin::SOLVE_SYMBOL).map_err(error)?;
+            let free = *library.get::<FreeFn>(plugin::FREE_SYMBOL).map_err(error)?;
+
+            Ok(Plugin { path: path.to_path_buf(), info, solve, free, _library: library })
+        }
+    }
+
+    pub fn solve(&self, part: u8, input: &str) -> Result<String, String> {
+        let mut answer: *mut c_char = ptr::null_mut();
+
+        unsafe {
+            let status = (self.solve)(part, input.as_ptr(), input.len(), &mut answer);
+            let text = if answer.is_null() {
+                String::new()
+            } else {
+                let text = CStr::from_ptr(answer).to_string_lossy().into_owned();
+                (self.free)(answer);
+                text
+            };
+
+            match status {
+                plugin::OK => Ok(text),
+                plugin::NO_SUCH_PART => Err(format!("there's no part {}", part)),
+                _ => Err(text),
+            }
+        }
+    }
+}
+
+// Shared libraries in the folder, e.g. `libday9.so`, sorted by name
+fn candidates(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Couldn't read {}: {}", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| {
+                    name.starts_with(env::consts::DLL_PREFIX)
+                        && name.ends_with(env::consts::DLL_SUFFIX)
+                })
+                .unwrap_or(false)
+        })
+        .collect();
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Loads the plugins in the folder, by day. Libraries that aren't plugins,
+/// or were built for another ABI, are reported and skipped.
+pub fn discover(dir: &Path) -> Result<Vec<Plugin>, String> {
+    let mut plugins: Vec<Plugin> = Vec::new();
+
+    for path in candidates(dir)? {
+        match Plugin::load(&path) {
+            Ok(p) => match plugins.iter().find(|q| q.info.day == p.info.day) {
+                Some(q) => eprintln!(
+                    "Skipping {}: Day {} is already solved by {}",
+                    path.display(), p.info.day, q.path.display(),
+                ),
+                None => plugins.push(p),
+            },
+            Err(e) => eprintln!("Skipping {}", e),
+        }
+    }
+
+    plugins.sort_by_key(|p| p.info.day);
+    Ok(plugins)
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let plugins = discover(&options.dir)?;
+    let selected: Vec<&Plugin> = plugins
+        .iter()
+        .filter(|p| options.day.is_none() || options.day == Some(p.info.day))
+        .collect();
+
+    if selected.is_empty() {
+        return Err(match options.day {
+            Some(day) => format!("There's no plugin for Day {} in {}", day, options.dir.display()),
+            None => format!("There are no plugins in {}", options.dir.display()),
+        });
+    }
+
+    for p in selected {
+        let input = if p.info.reads_input {
+            let path = options.input
+                .clone()
+                .unwrap_or_else(|| watch::day_dir(p.info.day).join("data/input.txt"));
+            fs::read_to_string(&path)
+                .map_err(|e| format!("Couldn't open {}: {}", path.display(), e))?
+        } else if options.input.is_some() {
+            return Err(format!("Day {} has its input in the code", p.info.day));
+        } else {
+            String::new()
+        };
+
+        for part in 1..=p.info.parts {
+            let start = Instant::now();
+            match p.solve(part, &input) {
+                Ok(answer) => println!(
+                    "Day {} / Part {}: {} ({:.1?})",
+                    p.info.day, part, answer, start.elapsed(),
+                ),
+                Err(e) => println!("Day {} / Part {} failed: {}", p.info.day, part, e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            Ok(Options { dir: PathBuf::from("plugins"), day: Some(5), input: None }),
+            Options::parse(&args(&["--plugins", "plugins", "--day", "5"])),
+        );
+        assert_eq!(Err("Missing --plugins".to_string()), Options::parse(&args(&[])));
+        assert!(Options::parse(&args(&["--plugins", "p", "--input", "x.txt"])).is_err());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let dir = env::temp_dir().join(format!("aoc-plugins-test-{}", process::id()));
+        let lib = |name: &str| {
+            dir.join(format!("{}{}{}", env::consts::DLL_PREFIX, name, env::consts::DLL_SUFFIX))
+        };
+        fs::create_dir_all(&dir).unwrap();
+        for path in [lib("day9"), lib("day1"), dir.join("day1.d"), dir.join("README.md")].iter() {
+            fs::write(path, "").unwrap();
+        }
+
+        assert_eq!(vec![lib("day1"), lib("day9")], candidates(&dir).unwrap());
+        // Not a real library, so it's skipped
+        assert!(discover(&dir).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+// MIT License
+//
+// Copyright (c) 2020 Pedro Rodrigues
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Re-runs a day whenever its input, examples or source change.
+//!
+//! The day's `data` and `src` folders are polled for modification times. On
+//! every change the day is rebuilt and run again, and its answers and running
+//! time are compared with the previous run.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u8,
+    pub interval: Duration,
+    // Handed over to the day, e.g. `--explain`
+    pub args: Vec<String>,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut day = None;
+        let mut interval = Duration::from_millis(500);
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let n = iter.next().ok_or("--day needs a value")?;
+                    day = Some(n.parse::<u8>().map_err(|_| format!("Invalid day: {}", n))?);
+                },
+                "--interval" => {
+                    let ms = iter.next().ok_or("--interval needs a value")?;
+                    let ms = ms.parse::<u64>().map_err(|_| format!("Invalid interval: {}", ms))?;
+                    interval = Duration::from_millis(ms);
+                },
+                "--" => break,
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        Ok(Self {
+            day: day.ok_or("Missing --day")?,
+            interval,
+            args: iter.cloned().collect(),
+        })
+    }
+}
+
+// Modification time of every file in the watched folders
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+fn snapshot(dirs: &[PathBuf]) -> Snapshot {
+    fn visit(dir: &Path, acc: &mut Snapshot) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.metadata() {
+                Ok(m) if m.is_dir() => visit(&path, acc),
+                Ok(m) => { acc.insert(path, m.modified().unwrap_or(SystemTime::UNIX_EPOCH)); },
+                Err(_) => {},
+            }
+        }
+    }
+
+    let mut acc = Snapshot::new();
+    dirs.iter().for_each(|dir| visit(dir, &mut acc));
+    acc
+}
+
+/// Files that were added, modified or removed between both snapshots.
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(time))
+        .map(|(path, _)| path.clone())
+        .chain(before.keys().filter(|path| !after.contains_key(*path)).cloned())
+        .collect();
+
+    changed.sort();
+    changed
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    stdout: String,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    // Answer lines look like `Day 9 / Part 1: 10884537`
+    fn answers(&self) -> HashMap<&str, &str> {
+        self.stdout
+            .lines()
+            .filter(|line| line.starts_with("Day ") && line.contains(" / Part "))
+            .filter_map(|line| {
+                let i = line.find(": ")?;
+                Some((&line[..i], &line[i+2..]))
+            })
+            .collect()
+    }
+}
+
+/// The output of the current run, with every answer compared to the one
+/// from the previous run, followed by the running times of both.
+fn report(previous: Option<&Outcome>, current: &Outcome) -> String {
+    let before = previous.map(|p| p.answers()).unwrap_or_default();
+    let after = current.answers();
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in current.stdout.lines() {
+        let answer = line.find(": ").and_then(|i| Some((after.get(&line[..i])?, &line[..i])));
+
+        lines.push(match (previous, answer) {
+            (None, _) | (_, None) => line.to_string(),
+            (Some(_), Some((new, part))) => match before.get(part) {
+                None => format!("{} (new)", line),
+                Some(old) if old == new => format!("{} (unchanged)", line),
+                Some(old) => format!("{} (was {})", line, old),
+            },
+        });
+    }
+
+    lines.push(match previous {
+        None => format!("Solved in {:.1?}", current.elapsed),
+        Some(p) => format!("Solved in {:.1?} (was {:.1?})", current.elapsed, p.elapsed),
+    });
+
+    lines.join("\n")
+}
+
+pub fn solve(dir: &Path, day: u8, args: &[String]) -> Result<Outcome, String> {
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--quiet"])
+        .current_dir(dir)
+        .status()
+        .map_err(|e| format!("Couldn't run cargo: {}", e))?;
+
+    if !status.success() {
+        return Err(format!("Day{} failed to build", day));
+    }
+
+    let target = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dir.join("target"));
+    let exe = target
+        .join("release")
+        .join(format!("Day{}{}", day, env::consts::EXE_SUFFIX));
+
+    let start = Instant::now();
+    let output = Command::new(&exe)
+        .args(args)
+        .current_dir(dir)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Couldn't run {}: {}", exe.display(), e))?;
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        return Err(format!("Day{} exited with {}", day, output.status));
+    }
+
+    Ok(Outcome { stdout: String::from_utf8_lossy(&output.stdout).into_owned(), elapsed })
+}
+
+/// The folder of the day's crate.
+pub fn day_dir(day: u8) -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    root.join(format!("Day{}", day))
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let dir = day_dir(options.day);
+    if !dir.is_dir() {
+        return Err(format!("There's no {}", dir.display()));
+    }
+
+    let watched = vec![dir.join("data"), dir.join("src")];
+    let mut files = snapshot(&watched);
+    let mut previous: Option<Outcome> = None;
+
+    println!("Watching {} (Ctrl-C to stop)", dir.display());
+
+    loop {
+        match solve(&dir, options.day, &options.args) {
+            Ok(outcome) => {
+                println!("{}", report(previous.as_ref(), &outcome));
+                previous = Some(outcome);
+            },
+            Err(e) => eprintln!("{}", e),
+        }
+
+        let changed = loop {
+            thread::sleep(options.interval);
+            let now = snapshot(&watched);
+            let changed = changes(&files, &now);
+            files = now;
+
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        println!();
+        for path in changed {
+            println!("Changed: {}", path.strip_prefix(&dir).unwrap_or(&path).display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            Ok(Options {
+                day: 9,
+                interval: Duration::from_millis(100),
+                args: args(&["--explain"]),
+            }),
+            Options::parse(&args(&["--day", "9", "--interval", "100", "--", "--explain"])),
+        );
+        assert_eq!(Err("Missing --day".to_string()), Options::parse(&args(&[])));
+        assert!(Options::parse(&args(&["--day", "x"])).is_err());
+        assert!(Options::parse(&args(&["--explain"])).is_err());
+    }
+
+    #[test]
+    fn test_changes() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let snap = |files: &[(&str, SystemTime)]| -> Snapshot {
+            files.iter().map(|(p, t)| (PathBuf::from(p), *t)).collect()
+        };
+
+        let before = snap(&[("data/input.txt", t0), ("src/main.rs", t0), ("data/old.txt", t0)]);
+        let after = snap(&[("data/input.txt", t1), ("src/main.rs", t0), ("data/sample.txt", t0)]);
+
+        assert!(changes(&before, &before).is_empty());
+        assert_eq!(
+            vec![
+                PathBuf::from("data/input.txt"),
+                PathBuf::from("data/old.txt"),
+                PathBuf::from("data/sample.txt"),
+            ],
+            changes(&before, &after),
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let first = Outcome {
+            stdout: "Day 9 / Part 1: 127\n".to_string(),
+            elapsed: Duration::from_millis(12),
+        };
+        let second = Outcome {
+            stdout: "Day 9 / Part 1: 127\nDay 9 / Part 2: 62\n    [range] 2..=5\n".to_string(),
+            elapsed: Duration::from_millis(10),
+        };
+        let third = Outcome {
+            stdout: "Day 9 / Part 1: 127\nDay 9 / Part 2: 63\n".to_string(),
+            elapsed: Duration::from_millis(11),
+        };
+
+        assert_eq!("Day 9 / Part 1: 127\nSolved in 12.0ms", report(None, &first));
+        assert_eq!(
+            "Day 9 / Part 1: 127 (unchanged)\n\
+             Day 9 / Part 2: 62 (new)\n    [range] 2..=5\n\
+             Solved in 10.0ms (was 12.0ms)",
+            report(Some(&first), &second),
+        );
+        assert_eq!(
+            "Day 9 / Part 1: 127 (unchanged)\n\
+             Day 9 / Part 2: 63 (was 62)\n\
+             Solved in 11.0ms (was 10.0ms)",
+            report(Some(&second), &third),
+        );
+    }
+}